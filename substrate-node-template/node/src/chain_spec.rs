@@ -153,5 +153,6 @@ fn testnet_genesis(
 		},
 		transaction_payment: Default::default(),
 		assets: Default::default(),
+		voting: Default::default(),
 	}
 }
@@ -0,0 +1,39 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait VotingApi<AccountId, Balance, ProposalIndex, ElectionResult, WinnerOutcome>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		ProposalIndex: Codec,
+		ElectionResult: Codec,
+		WinnerOutcome: Codec,
+	{
+		/// (account, registered, has_voted) of every account for the given proposal.
+		fn bulk_voting_status(
+			accounts: Vec<AccountId>,
+			proposal_index: ProposalIndex,
+		) -> Vec<(AccountId, bool, bool)>;
+
+		/// Winner reaching the quorum, runner-up, tallies, turnout and finalization block in a single struct.
+		fn election_result() -> ElectionResult;
+
+		/// Proposal with the most net votes, if any.
+		fn current_winner() -> Option<ProposalIndex>;
+
+		/// Leading proposal together with whether it reached the quorum and the pass threshold.
+		fn get_winner_outcome() -> WinnerOutcome;
+
+		/// (ayes, nays) of the given proposal, None if it doesn't exist.
+		fn proposal_tally(proposal_index: ProposalIndex) -> Option<(Balance, Balance)>;
+
+		/// Indexes of the proposals in progress.
+		fn active_proposals() -> Vec<ProposalIndex>;
+
+		/// Whether the given proposal can be voted on at the current block.
+		fn is_voting_open(proposal_index: ProposalIndex) -> bool;
+	}
+}
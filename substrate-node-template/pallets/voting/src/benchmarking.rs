@@ -1,20 +1,630 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-voting
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as Voting;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	assert_ok,
+	inherent::Vec,
+	sp_runtime::traits::Hash,
+	traits::{Currency, ReservableCurrency},
+};
 use frame_system::RawOrigin;
 
+const SEED: u32 = 0;
+
+// Registered voter with enough free balance to start proposals and reserve tokens
+fn setup_voter<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	assert_ok!(Voting::<T>::register_voter(
+		RawOrigin::Root.into(),
+		caller.clone(),
+		5u32.into(),
+		false
+	));
+	T::Currency::make_free_balance_be(&caller, 1_000_000u32.into());
+	caller
+}
+
+// Registered voter owning the voted proposals, in case AllowSelfVoting is disabled
+fn setup_proposer<T: Config>() -> T::AccountId {
+	let proposer: T::AccountId = account("proposer", 0, SEED);
+	assert_ok!(Voting::<T>::register_voter(
+		RawOrigin::Root.into(),
+		proposer.clone(),
+		5u32.into(),
+		false
+	));
+	T::Currency::make_free_balance_be(&proposer, 1_000_000u32.into());
+	proposer
+}
+
+// Creates `count` proposals, or as many as MinProposalsToVote requires to vote
+// (so a count of 0 just lets the proposer vote)
+fn create_proposals<T: Config>(proposer: &T::AccountId, count: u32) {
+	for i in 0..count.max(T::MinProposalsToVote::get()) {
+		let text = i.to_le_bytes().to_vec();
+		assert_ok!(Voting::<T>::create_proposal(
+			RawOrigin::Signed(proposer.clone()).into(),
+			text,
+			None,
+			None
+		));
+	}
+}
+
+fn start_proposal<T: Config>(proposer: &T::AccountId, proposal_index: ProposalIndexOf<T>) {
+	assert_ok!(Voting::<T>::start_proposal(
+		RawOrigin::Signed(proposer.clone()).into(),
+		proposal_index,
+		10u32.into()
+	));
+}
+
+// Closes a started proposal once its voting and reveal periods are over
+fn finish_proposal<T: Config>(closer: &T::AccountId, proposal_index: ProposalIndexOf<T>) {
+	let proposal = Voting::<T>::get_proposal(proposal_index).expect("Proposal started before");
+	let completion_block = Voting::<T>::completion_block(proposal.end_block);
+	frame_system::Pallet::<T>::set_block_number(completion_block);
+	assert_ok!(Voting::<T>::close_proposal(
+		RawOrigin::Signed(closer.clone()).into(),
+		proposal_index
+	));
+}
+
+// Registers `count` voters delegating straight to `to`
+fn setup_delegators<T: Config>(to: &T::AccountId, count: u32) {
+	for i in 0..count {
+		let delegator: T::AccountId = account("delegator", i, SEED);
+		assert_ok!(Voting::<T>::register_voter(
+			RawOrigin::Root.into(),
+			delegator.clone(),
+			5u32.into(),
+			false
+		));
+		assert_ok!(Voting::<T>::delegate(RawOrigin::Signed(delegator).into(), to.clone()));
+	}
+}
+
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
-		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+	register_voter {
+		let voter: T::AccountId = account("voter", 0, SEED);
+	}: _(RawOrigin::Root, voter.clone(), 5u32.into(), false)
+	verify {
+		assert!(Voting::<T>::is_voter_registered(&voter));
+	}
+
+	create_proposal {
+		let caller = setup_voter::<T>();
+		let text: Vec<u8> = (0..T::MaxProposalTextLen::get()).map(|_| 1u8).collect();
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+	}: _(RawOrigin::Signed(caller), text, None, None)
+	verify {
+		assert!(Voting::<T>::is_proposal_registered(proposal_index));
+		assert!(ProposalTexts::<T>::contains_key(proposal_index));
+	}
+
+	start_proposal {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+	}: _(RawOrigin::Signed(caller), proposal_index, 10u32.into())
+	verify {
+		assert_eq!(
+			Voting::<T>::get_proposal_status(proposal_index),
+			Ok(ProposalStatus::InProgress)
+		);
+	}
+
+	reserve_tokens {
+		let caller = setup_voter::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), 50u32.into())
+	verify {
+		assert_eq!(T::Currency::reserved_balance(&caller), 50u32.into());
+	}
+
+	vote_proposal {
+		// The reserve of every delegator is read for the tokens the caller can vote with
+		let d in 0 .. T::MaxDelegators::get();
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		setup_delegators::<T>(&caller, d);
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		// Reserved before the start, in case RequirePreReserve is enabled
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, 49u32.into(), Vote::Aye, 0)
+	verify {
+		assert!(AyeVotes::<T>::contains_key(proposal_index, &caller));
+	}
+
+	unreserve_tokens {
+		let caller = setup_voter::<T>();
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+	}: _(RawOrigin::Signed(caller.clone()), 10u32.into())
+	verify {
+		assert_eq!(T::Currency::reserved_balance(&caller), 40u32.into());
+	}
+
+	vote_multiple_proposals {
+		let v in 1 .. T::MaxVoted::get();
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, v);
+		create_proposals::<T>(&caller, 0);
+		assert_ok!(Voting::<T>::reserve_tokens(
+			RawOrigin::Signed(caller.clone()).into(),
+			(10 * v).into()
+		));
+		let proposals: Vec<(ProposalIndexOf<T>, BalanceOf<T>, Vote)> = (1..=v)
+			.map(|proposal_index| (proposal_index.into(), 10u32.into(), Vote::Aye))
+			.collect();
+		for proposal_index in 1..=v {
+			start_proposal::<T>(&proposer, proposal_index.into());
+		}
+	}: _(RawOrigin::Signed(caller.clone()), proposals)
+	verify {
+		assert_eq!(Voting::<T>::proposals_voted_by(&caller).len(), v as usize);
+	}
+
+	change_vote {
+		let d in 0 .. T::MaxDelegators::get();
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		// Delegated before the caller votes, committed tokens can't be delegated to
+		setup_delegators::<T>(&caller, d);
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		assert_ok!(Voting::<T>::vote_proposal(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_index,
+			16u32.into(),
+			Vote::Aye,
+			0
+		));
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, Vote::Nay, 25u32.into())
+	verify {
+		assert!(NayVotes::<T>::contains_key(proposal_index, &caller));
+	}
+
+	complete_proposal {
+		let p in 1 .. T::MaxProposals::get();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, p);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		let proposal = Voting::<T>::get_proposal(proposal_index).expect("Proposal created above");
+		let completion_block = Voting::<T>::completion_block(proposal.end_block);
+		frame_system::Pallet::<T>::set_block_number(completion_block);
+	}: {
+		Voting::<T>::complete_expiring_proposals(completion_block);
+	}
+	verify {
+		assert!(!Voting::<T>::is_proposal_active(proposal_index));
+	}
+
+	invalidate_result {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&caller, proposal_index);
+		finish_proposal::<T>(&caller, proposal_index);
+	}: _(RawOrigin::Root, proposal_index)
+	verify {
+		assert_eq!(
+			Voting::<T>::get_proposal_status(proposal_index),
+			Ok(ProposalStatus::InProgress)
+		);
+	}
+
+	reserve_for {
+		let caller = setup_voter::<T>();
+		let beneficiary = setup_proposer::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), beneficiary.clone(), 50u32.into())
+	verify {
+		assert_eq!(SponsoredIn::<T>::get(&beneficiary), 50u32.into());
+	}
+
+	recompute_pending_end_blocks {
+		let p in 1 .. T::MaxProposals::get();
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, p);
+	}: _(RawOrigin::Root)
+	verify {
+		let proposal = Voting::<T>::get_proposal(1u32.into()).expect("Proposal created above");
+		assert_eq!(proposal.end_block, proposal.created_at + T::VotingPeriod::get());
+	}
+
+	vote_with_conviction {
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		let periods = T::MaxLockPeriods::get();
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, 49u32.into(), periods, Vote::Aye)
+	verify {
+		assert!(AyeVotes::<T>::contains_key(proposal_index, &caller));
+	}
+
+	unlock_tokens {
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		assert_ok!(Voting::<T>::vote_with_conviction(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_index,
+			49u32.into(),
+			1,
+			Vote::Aye
+		));
+		let (_, until) = ConvictionLocks::<T>::get(&caller).expect("Lock created above");
+		frame_system::Pallet::<T>::set_block_number(until);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!ConvictionLocks::<T>::contains_key(&caller));
+	}
+
+	emit_winner {
+		let p in 1 .. T::MaxProposals::get();
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, p);
+		for proposal_index in 1..=p {
+			start_proposal::<T>(&caller, proposal_index.into());
+		}
+	}: _(RawOrigin::Signed(caller))
+
+	archive_draft {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+	}: _(RawOrigin::Signed(caller), proposal_index)
+	verify {
+		assert!(Drafts::<T>::contains_key(proposal_index));
+		assert!(!Voting::<T>::is_proposal_registered(proposal_index));
+	}
+
+	restore_draft {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		assert_ok!(Voting::<T>::archive_draft(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_index
+		));
+	}: _(RawOrigin::Signed(caller), proposal_index)
+	verify {
+		assert!(Voting::<T>::is_proposal_registered(proposal_index));
+	}
+
+	fund_reward_pool {
+		let caller = setup_voter::<T>();
+		// The pool account has to end up above the existential deposit
+		let amount = T::Currency::minimum_balance() + 100u32.into();
+	}: _(RawOrigin::Signed(caller), amount)
+	verify {
+		assert_eq!(Voting::<T>::reward_pool_balance(), amount);
+	}
+
+	cancel_proposal {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+	}: _(RawOrigin::Signed(caller), proposal_index)
+	verify {
+		assert!(!Voting::<T>::is_proposal_registered(proposal_index));
+	}
+
+	unregister_voter {
+		let p in 1 .. T::MaxProposals::get();
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, p);
+		create_proposals::<T>(&caller, 0);
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		assert_ok!(Voting::<T>::vote_proposal(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_index,
+			49u32.into(),
+			Vote::Aye,
+			0
+		));
+	}: _(RawOrigin::Root, caller.clone())
+	verify {
+		assert!(!Voting::<T>::is_voter_registered(&caller));
+	}
+
+	delegate {
+		// The whole tree behind the caller moves, so it can hold one voter less than the cap
+		let d in 0 .. T::MaxDelegators::get() - 1;
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		setup_delegators::<T>(&caller, d);
+	}: _(RawOrigin::Signed(caller.clone()), proposer.clone())
+	verify {
+		assert_eq!(Voting::<T>::delegators_of(&proposer).len(), d as usize + 1);
+	}
+
+	undelegate {
+		// Chain of d delegations, walked from the caller up to its root
+		let d in 1 .. T::MaxDelegators::get();
+		let caller = setup_voter::<T>();
+		let mut from = caller.clone();
+		for i in 0..d {
+			let to: T::AccountId = account("delegate", i, SEED);
+			assert_ok!(Voting::<T>::register_voter(
+				RawOrigin::Root.into(),
+				to.clone(),
+				5u32.into(),
+				false
+			));
+			assert_ok!(Voting::<T>::delegate(RawOrigin::Signed(from).into(), to.clone()));
+			from = to;
+		}
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Delegations::<T>::contains_key(&caller));
+	}
+
+	commit_vote {
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		let tokens: BalanceOf<T> = 49u32.into();
+		let commitment = T::Hashing::hash_of(&(&Vote::Aye, tokens, T::Hash::default()));
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, commitment)
+	verify {
+		assert!(VoteCommitments::<T>::contains_key(proposal_index, &caller));
+	}
+
+	reveal_vote {
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		let tokens: BalanceOf<T> = 49u32.into();
+		let salt = T::Hash::default();
+		assert_ok!(Voting::<T>::commit_vote(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_index,
+			T::Hashing::hash_of(&(&Vote::Aye, tokens, salt))
+		));
+		// Hidden votes are revealed once the voting period is over
+		let proposal = Voting::<T>::get_proposal(proposal_index).expect("Proposal started above");
+		frame_system::Pallet::<T>::set_block_number(proposal.end_block);
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, Vote::Aye, tokens, salt)
+	verify {
+		assert!(AyeVotes::<T>::contains_key(proposal_index, &caller));
+	}
+
+	claim_refund {
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		assert_ok!(Voting::<T>::vote_proposal(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_index,
+			49u32.into(),
+			Vote::Aye,
+			0
+		));
+		finish_proposal::<T>(&caller, proposal_index);
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index)
+	verify {
+		assert!(!CommittedTokens::<T>::contains_key(&caller, proposal_index));
+	}
+
+	reject_proposal {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&caller, proposal_index);
+	}: _(RawOrigin::Root, proposal_index)
+	verify {
+		assert_eq!(
+			Voting::<T>::get_proposal_status(proposal_index),
+			Ok(ProposalStatus::Rejected)
+		);
+	}
+
+	vote_with_credits {
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, Vote::Aye, 7u32.into())
+	verify {
+		assert!(AyeVotes::<T>::contains_key(proposal_index, &caller));
+	}
+
+	register_voters {
+		let v in 1 .. T::MaxBatchSize::get();
+		let voters: Vec<(T::AccountId, BalanceOf<T>)> =
+			(0..v).map(|i| (account("voter", i, SEED), 5u32.into())).collect();
+		let last_voter: T::AccountId = account("voter", v - 1, SEED);
+	}: _(RawOrigin::Root, voters)
+	verify {
+		assert!(Voting::<T>::is_voter_registered(&last_voter));
+	}
+
+	close_proposal {
+		let p in 1 .. T::MaxProposals::get();
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, p);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&caller, proposal_index);
+		let proposal = Voting::<T>::get_proposal(proposal_index).expect("Proposal started above");
+		let completion_block = Voting::<T>::completion_block(proposal.end_block);
+		frame_system::Pallet::<T>::set_block_number(completion_block);
+	}: _(RawOrigin::Signed(caller), proposal_index)
+	verify {
+		assert_eq!(
+			Voting::<T>::get_proposal_status(proposal_index),
+			Ok(ProposalStatus::Completed)
+		);
+	}
+
+	add_votes {
+		let d in 0 .. T::MaxDelegators::get();
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		// Delegated before the caller votes, committed tokens can't be delegated to
+		setup_delegators::<T>(&caller, d);
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&proposer, proposal_index);
+		assert_ok!(Voting::<T>::vote_proposal(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_index,
+			16u32.into(),
+			Vote::Aye,
+			0
+		));
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, Vote::Aye, 9u32.into())
+	verify {
+		assert_eq!(CommittedTokens::<T>::get(&caller, proposal_index), 25u32.into());
+	}
+
+	set_proposal_options {
+		let o in 0 .. T::MaxOptions::get();
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		let options: Vec<T::Hash> = (0..o).map(|i| T::Hashing::hash_of(&i)).collect();
+	}: _(RawOrigin::Signed(caller), proposal_index, options)
+	verify {
+		let proposal = Voting::<T>::get_proposal(proposal_index).expect("Proposal created above");
+		assert_eq!(proposal.options.len(), o as usize);
+	}
+
+	vote_option {
+		let d in 0 .. T::MaxDelegators::get();
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		setup_delegators::<T>(&caller, d);
+		create_proposals::<T>(&proposer, 1);
+		create_proposals::<T>(&caller, 0);
+		assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(caller.clone()).into(), 50u32.into()));
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		let options: Vec<T::Hash> =
+			(0..T::MaxOptions::get()).map(|i| T::Hashing::hash_of(&i)).collect();
+		assert_ok!(Voting::<T>::set_proposal_options(
+			RawOrigin::Signed(proposer.clone()).into(),
+			proposal_index,
+			options
+		));
+		start_proposal::<T>(&proposer, proposal_index);
+	}: _(RawOrigin::Signed(caller.clone()), proposal_index, 0, 49u32.into())
+	verify {
+		assert!(OptionVotes::<T>::contains_key((proposal_index, 0u32), &caller));
+	}
+
+	force_start_proposal {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+	}: _(RawOrigin::Root, proposal_index)
+	verify {
+		assert_eq!(
+			Voting::<T>::get_proposal_status(proposal_index),
+			Ok(ProposalStatus::InProgress)
+		);
+	}
+
+	pause {
+	}: _(RawOrigin::Root)
+	verify {
+		assert!(Voting::<T>::is_paused());
+	}
+
+	resume {
+		assert_ok!(Voting::<T>::pause(RawOrigin::Root.into()));
+	}: _(RawOrigin::Root)
+	verify {
+		assert!(!Voting::<T>::is_paused());
+	}
+
+	extend_proposal {
+		let caller = setup_voter::<T>();
+		create_proposals::<T>(&caller, 1);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		start_proposal::<T>(&caller, proposal_index);
+		let proposal = Voting::<T>::get_proposal(proposal_index).expect("Proposal started above");
+	}: _(RawOrigin::Root, proposal_index, 10u32.into())
+	verify {
+		assert_eq!(
+			Voting::<T>::get_proposal_end_block(proposal_index),
+			Ok(proposal.end_block + 10u32.into())
+		);
+	}
+
+	late_vote {
+		// Completing the proposal ranks every proposal and, with AutoUnreserveOnCompletion,
+		// refunds every voter
+		let p in 1 .. T::MaxProposals::get();
+		let v in 0 .. T::MaxVotersPerProposal::get();
+		let caller = setup_voter::<T>();
+		let proposer = setup_proposer::<T>();
+		create_proposals::<T>(&proposer, p);
+		let proposal_index: ProposalIndexOf<T> = 1u32.into();
+		let voters: Vec<T::AccountId> = (0..v).map(|i| account("voter", i, SEED)).collect();
+		for voter in voters.iter() {
+			assert_ok!(Voting::<T>::register_voter(
+				RawOrigin::Root.into(),
+				voter.clone(),
+				5u32.into(),
+				false
+			));
+			T::Currency::make_free_balance_be(voter, 1_000_000u32.into());
+			assert_ok!(Voting::<T>::reserve_tokens(RawOrigin::Signed(voter.clone()).into(), 50u32.into()));
+		}
+		start_proposal::<T>(&proposer, proposal_index);
+		for voter in voters {
+			assert_ok!(Voting::<T>::vote_proposal(
+				RawOrigin::Signed(voter).into(),
+				proposal_index,
+				49u32.into(),
+				Vote::Aye,
+				0
+			));
+		}
+		let proposal = Voting::<T>::get_proposal(proposal_index).expect("Proposal started above");
+		let completion_block = Voting::<T>::completion_block(proposal.end_block);
+		frame_system::Pallet::<T>::set_block_number(completion_block);
+	}: vote_proposal(RawOrigin::Signed(caller), proposal_index, 49u32.into(), Vote::Aye, 0)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(
+			Voting::<T>::get_proposal_status(proposal_index),
+			Ok(ProposalStatus::Completed)
+		);
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(Voting, crate::mock::new_bench_ext(), crate::mock::Test);
 }
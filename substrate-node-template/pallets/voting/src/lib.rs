@@ -2,26 +2,51 @@
 pub use pallet::*;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub};
-	use frame_support::sp_runtime::SaturatedConversion;
+	use crate::weights::WeightInfo;
 	use frame_support::{
 		inherent::Vec,
+		log,
 		pallet_prelude::{CountedStorageMap, *},
-		traits::{Currency, LockableCurrency, ReservableCurrency},
-		Blake2_128Concat,
+		sp_runtime::{
+			traits::{
+				AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedSub,
+				Hash, Saturating,
+			},
+			PerThing, Percent, SaturatedConversion,
+		},
+		traits::{
+			Currency, ExistenceRequirement, Imbalance, LockIdentifier, LockableCurrency,
+			OnUnbalanced, ReservableCurrency, WithdrawReasons,
+		},
+		Blake2_128Concat, PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use integer_sqrt::IntegerSquareRoot;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
+	const VOTING_ID: LockIdentifier = *b"qvoting ";
+	/// Vote multiplier of each conviction a vote_proposal can be cast with.
+	pub const CONVICTION_MULTIPLIERS: [u32; 3] = [1, 2, 4];
+	const LOG_TARGET: &str = "runtime::voting";
+	/// Blake2-256 hash of the SCALE encoded empty string.
+	pub const EMPTY_TEXT_HASH: [u8; 32] = [
+		0x03, 0x17, 0x0a, 0x2e, 0x75, 0x97, 0xb7, 0xb7, 0xe3, 0xd8, 0x4c, 0x05, 0x39, 0x1d, 0x13,
+		0x9a, 0x62, 0xb1, 0x57, 0xe7, 0x87, 0x86, 0xd8, 0xc0, 0x82, 0xf2, 0x9d, 0xcf, 0x4c, 0x11,
+		0x13, 0x14,
+	];
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -36,6 +61,151 @@ pub mod pallet {
 
 		/// Voting period in blocks.
 		type VotingPeriod: Get<Self::BlockNumber>;
+
+		/// Whether voters must have reserved their tokens before the proposal started.
+		type RequirePreReserve: Get<bool>;
+
+		/// Whether registering an already registered voter is a no-op instead of an error.
+		type RegistrationIdempotent: Get<bool>;
+
+		/// Minimum number of proposals a voter must have created before voting.
+		type MinProposalsToVote: Get<u32>;
+
+		/// Minimum score the leading proposal needs to be considered passed.
+		type PassThreshold: Get<u128>;
+
+		/// Blocks after end_block during which root can invalidate a result.
+		type DisputeWindow: Get<Self::BlockNumber>;
+
+		/// Yield credited to reserved balances every YieldPeriod.
+		type ReserveYield: Get<Percent>;
+
+		/// Interval in blocks between yield rounds.
+		type YieldPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of voters processed per block during a yield round.
+		type MaxYieldPayoutsPerBlock: Get<u32>;
+
+		/// Whether votes of long-standing voters weigh more.
+		type AgeWeighting: Get<bool>;
+
+		/// Cap of the account age multiplier (one step per VotingPeriod since registration).
+		type MaxAgeMultiplier: Get<u32>;
+
+		/// Whether the start fee is held and returned once the proposal completes, instead of
+		/// going to the FeeHandler.
+		type RefundStartFee: Get<bool>;
+
+		/// Weight lost by a vote cast right at the end of the voting window.
+		type WeightDecay: Get<Percent>;
+
+		/// Length in blocks of one conviction lock period.
+		type LockPeriodLength: Get<Self::BlockNumber>;
+
+		/// Maximum number of lock periods a conviction vote can commit to.
+		type MaxLockPeriods: Get<u32>;
+
+		/// Whether a voter may vote the same proposal more than once.
+		type AllowRepeatVotes: Get<bool>;
+
+		/// Weight discount applied on every repeated vote on the same proposal.
+		type RepeatVoteDiscount: Get<Percent>;
+
+		/// Maximum number of voters scanned when measuring the influence concentration.
+		type MaxConcentrationVoters: Get<u32>;
+
+		/// Whether committed tokens are unreserved, without penalty, once a proposal completes.
+		type AutoUnreserveOnCompletion: Get<bool>;
+
+		/// Maximum reserve all voters together can commit to a single proposal.
+		type MaxProposalReserve: Get<BalanceOf<Self>>;
+
+		/// Maximum number of proposals tracked per voter in VotedProposals.
+		type MaxVoted: Get<u32>;
+
+		/// Lead over the runner-up, relative to its tally, needed for a conclusive win.
+		type WinMargin: Get<Percent>;
+
+		/// Maximum number of distinct voters a single proposal can have.
+		type MaxVotersPerProposal: Get<u32>;
+
+		/// Maximum number of accounts answered by a single bulk_voting_status query.
+		type MaxBulkStatusAccounts: Get<u32>;
+
+		/// Blocks after creation within which a proposal must be started (0 disables the expiry).
+		type ProposalStartDeadline: Get<Self::BlockNumber>;
+
+		/// Whether proposals with the hash of an empty text are rejected.
+		type RejectEmptyText: Get<bool>;
+
+		/// Identifier of the account holding the reward pool.
+		type PalletId: Get<PalletId>;
+
+		/// Reward paid from the pool to the proposer of the winning proposal.
+		type PoolReward: Get<BalanceOf<Self>>;
+
+		/// Maximum length in bytes of the text stored with a proposal.
+		type MaxProposalTextLen: Get<u32>;
+
+		/// Minimum number of distinct voters a proposal needs to win.
+		type MinQuorum: Get<u32>;
+
+		/// Tokens minted to the proposer of the winning proposal when voting ends.
+		type WinnerReward: Get<BalanceOf<Self>>;
+
+		/// Handler of the proposal, registration and start fees and of the unreserve slash.
+		type FeeHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Maximum number of stored proposals.
+		type MaxProposals: Get<u32>;
+
+		/// Shortest voting period a proposer can choose.
+		type MinVotingPeriod: Get<Self::BlockNumber>;
+
+		/// Longest voting period a proposer can choose.
+		type MaxVotingPeriod: Get<Self::BlockNumber>;
+
+		/// Whether proposers may vote their own proposals.
+		type AllowSelfVoting: Get<bool>;
+
+		/// Maximum number of voters registered in one batch.
+		type MaxBatchSize: Get<u32>;
+
+		/// Type used to index the proposals.
+		type ProposalIndex: Parameter + Member + AtLeast32BitUnsigned + Copy + MaxEncodedLen;
+
+		/// Maximum votes a single account can hold on a proposal, after the quadratic root.
+		type MaxVotesPerVoter: Get<u32>;
+
+		/// Maximum number of options of a multi-choice proposal.
+		type MaxOptions: Get<u32>;
+
+		/// Smallest amount of tokens that can be reserved at once.
+		type MinReserveAmount: Get<BalanceOf<Self>>;
+
+		/// Maximum length of the off-chain metadata of a proposal.
+		type MaxMetadataLen: Get<u32>;
+
+		/// Fee withdrawn from the proposer at proposal creation, 0 to make creation free.
+		type ProposalCreationFee: Get<BalanceOf<Self>>;
+
+		/// Deposit reserved from the proposer at proposal creation, returned on quorum.
+		type ProposalDeposit: Get<BalanceOf<Self>>;
+
+		/// Tokens the proposer must hold reserved to start a proposal, 0 to disable the check.
+		type MinProposerReserve: Get<BalanceOf<Self>>;
+
+		/// Maximum number of voters delegating to a voter, directly or through others.
+		type MaxDelegators: Get<u32>;
+
+		/// Blocks after the end of a proposal during which hidden votes can be revealed.
+		type RevealPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of proposals the expiry hook completes in a single block.
+		type MaxCompletionsPerBlock: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 	// I use some type alias to make the code more readable (I also use this types on my tests)
 	pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -43,7 +213,13 @@ pub mod pallet {
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
-	pub type ProposalIndex = u32;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	pub type ProposalIndexOf<T> = <T as Config>::ProposalIndex;
+
+	pub type OptionIndex = u32;
 
 	#[pallet::storage]
 	pub type RegisteredVoters<T: Config> =
@@ -51,27 +227,330 @@ pub mod pallet {
 
 	#[pallet::storage]
 	pub type Proposals<T: Config> =
-		CountedStorageMap<_, Blake2_128Concat, ProposalIndex, Proposal<T>, OptionQuery>;
+		CountedStorageMap<_, Blake2_128Concat, ProposalIndexOf<T>, Proposal<T>, OptionQuery>;
+
+	/// Index the next created proposal will get (0 until the first proposal is created).
+	#[pallet::storage]
+	pub type NextProposalIndex<T: Config> = StorageValue<_, ProposalIndexOf<T>, ValueQuery>;
+
+	/// Not started proposals archived by their proposer.
+	#[pallet::storage]
+	pub type Drafts<T: Config> =
+		CountedStorageMap<_, Blake2_128Concat, ProposalIndexOf<T>, Proposal<T>, OptionQuery>;
+
+	/// Block at which each voter was registered.
+	#[pallet::storage]
+	pub type RegisteredAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, T::BlockNumber, OptionQuery>;
+
+	/// Number of proposals created by each account.
+	#[pallet::storage]
+	pub type ProposalsByProposer<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
+
+	/// Proposals created by each account, in creation order.
+	#[pallet::storage]
+	pub type ProposerIndex<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BoundedVec<ProposalIndexOf<T>, T::MaxProposals>,
+		ValueQuery,
+	>;
+
+	/// Block at which the voter's current reservation was first made.
+	#[pallet::storage]
+	pub type ReservedAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, T::BlockNumber, OptionQuery>;
+
+	/// Tokens locked by conviction votes and the block the lock expires at.
+	#[pallet::storage]
+	pub type ConvictionLocks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		(BalanceOf<T>, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// Reserved tokens locked by vote_proposal convictions and the block the lock expires at.
+	#[pallet::storage]
+	pub type ReserveLocks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		(BalanceOf<T>, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// Raw key of the last voter credited in the ongoing yield round (empty if it just started).
+	#[pallet::storage]
+	pub type YieldCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<128>>, OptionQuery>;
+
+	/// Tokens reserved by a sponsor on behalf of a beneficiary. [sponsor, beneficiary]
+	#[pallet::storage]
+	pub type Sponsorships<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Total tokens received from sponsors.
+	#[pallet::storage]
+	pub type SponsoredIn<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Total tokens reserved on behalf of other voters.
+	#[pallet::storage]
+	pub type SponsoredOut<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Total proposal deposits and start fees reserved by each account, they can't be used to vote.
+	#[pallet::storage]
+	pub type HeldDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Start fee of each proposal started while RefundStartFee was enabled, held on its payer.
+	#[pallet::storage]
+	pub type HeldStartFees<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		(AccountIdOf<T>, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// Voter each account delegated its voting power to.
+	#[pallet::storage]
+	pub type Delegations<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, AccountIdOf<T>, OptionQuery>;
+
+	/// Voters delegating directly to each account, the reverse of Delegations.
+	#[pallet::storage]
+	pub type Delegators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BoundedVec<AccountIdOf<T>, T::MaxDelegators>,
+		ValueQuery,
+	>;
+
+	/// Hidden votes of each proposal, as (hash of (vote, tokens, salt), commit block).
+	#[pallet::storage]
+	pub type VoteCommitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		(T::Hash, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// Reserved tokens each voter has committed to a proposal.
+	#[pallet::storage]
+	pub type CommittedTokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Reserve committed by all voters to each proposal.
+	#[pallet::storage]
+	pub type ProposalCommittedReserve<T: Config> =
+		StorageMap<_, Blake2_128Concat, ProposalIndexOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Proposals each voter has voted on.
+	#[pallet::storage]
+	pub type VotedProposals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BoundedVec<ProposalIndexOf<T>, T::MaxVoted>,
+		ValueQuery,
+	>;
+
+	/// Proposals each voter has cast a ballot on, kept after refunds.
+	#[pallet::storage]
+	pub type VoterBallots<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BoundedVec<ProposalIndexOf<T>, T::MaxProposals>,
+		ValueQuery,
+	>;
+
+	/// Number of distinct voters of each proposal.
+	#[pallet::storage]
+	pub type ProposalVoterCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, ProposalIndexOf<T>, u32, ValueQuery>;
+
+	/// Number of votes each voter cast on a proposal.
+	#[pallet::storage]
+	pub type VoteCounts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		u32,
+		ValueQuery,
+	>;
 
 	#[pallet::storage]
 	pub type AyeVotes<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		ProposalIndex,
+		ProposalIndexOf<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	pub type NayVotes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	// Proposals due to complete at a given block, so on_finalize doesn't scan them all
+	#[pallet::storage]
+	pub type ExpiringProposals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		(),
+		OptionQuery,
+	>;
+
+	/// Proposals scheduled to complete at each block, at most MaxCompletionsPerBlock.
+	#[pallet::storage]
+	pub type ExpiringCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, u32, ValueQuery>;
+
+	// Voters that took part in a proposal without taking a side
+	#[pallet::storage]
+	pub type AbstainVotes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		(),
+		OptionQuery,
+	>;
+
+	/// Winning proposals whose proposer was already rewarded, so re-completions don't pay again.
+	#[pallet::storage]
+	pub type RewardedProposals<T: Config> =
+		StorageMap<_, Blake2_128Concat, ProposalIndexOf<T>, (), OptionQuery>;
+
+	/// Running (ayes, nays) of each proposal, kept in sync with AyeVotes and NayVotes.
+	#[pallet::storage]
+	pub type ProposalTally<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		(BalanceOf<T>, BalanceOf<T>),
+		ValueQuery,
+	>;
+
+	/// Whether voting activity is halted by root.
+	#[pallet::storage]
+	pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Votes each voter gave to an option of a multi-choice proposal.
+	#[pallet::storage]
+	pub type OptionVotes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(ProposalIndexOf<T>, OptionIndex),
 		Blake2_128Concat,
 		AccountIdOf<T>,
 		BalanceOf<T>,
 		ValueQuery,
 	>;
 
+	/// Running votes of each option of a multi-choice proposal, kept in sync with OptionVotes.
+	#[pallet::storage]
+	pub type OptionTally<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		(ProposalIndexOf<T>, OptionIndex),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Raw text of each proposal, whose hash is kept in the proposal itself.
+	#[pallet::storage]
+	pub type ProposalTexts<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ProposalIndexOf<T>,
+		BoundedVec<u8, T::MaxProposalTextLen>,
+		OptionQuery,
+	>;
+
+	/// Duration chosen at creation by each proposal that doesn't follow VotingPeriod.
+	#[pallet::storage]
+	pub type CustomVotingPeriods<T: Config> =
+		StorageMap<_, Blake2_128Concat, ProposalIndexOf<T>, T::BlockNumber, OptionQuery>;
+
 	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Debug, Clone, PartialEq)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Proposal<T: Config> {
-		proposal_index: u32,
-		text: T::Hash,
-		proposer: AccountIdOf<T>,
-		end_block: T::BlockNumber,
-		status: ProposalStatus,
+		pub(crate) proposal_index: ProposalIndexOf<T>,
+		pub(crate) text: T::Hash,
+		pub(crate) proposer: AccountIdOf<T>,
+		pub(crate) end_block: T::BlockNumber,
+		pub(crate) start_block: T::BlockNumber,
+		pub(crate) start_fee: BalanceOf<T>,
+		pub(crate) created_at: T::BlockNumber,
+		pub(crate) completed_at: Option<T::BlockNumber>,
+		pub(crate) status: ProposalStatus,
+		pub(crate) options: BoundedVec<T::Hash, T::MaxOptions>,
+		pub(crate) metadata: Option<BoundedVec<u8, T::MaxMetadataLen>>,
+		pub(crate) deposit: BalanceOf<T>,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Debug, Clone, PartialEq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ProposalTimeline<T: Config> {
+		pub created_at: T::BlockNumber,
+		pub started_at: Option<T::BlockNumber>,
+		pub end_block: T::BlockNumber,
+		pub completed_at: Option<T::BlockNumber>,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, Debug, Clone, PartialEq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ElectionResult<T: Config> {
+		/// Leading proposal and its score, if it reached the quorum
+		pub winner: Option<(ProposalIndexOf<T>, u128)>,
+		/// Second proposal and its score
+		pub runner_up: Option<(ProposalIndexOf<T>, u128)>,
+		/// Score of every proposal, sorted by index
+		pub tallies: Vec<(ProposalIndexOf<T>, u128)>,
+		/// Distinct voters summed over all the proposals
+		pub turnout: u32,
+		/// Block at which the winner completed
+		pub finalized_at: Option<T::BlockNumber>,
 	}
 
 	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Debug, Clone, PartialEq)]
@@ -86,6 +565,47 @@ pub mod pallet {
 		NotStarted,
 		InProgress,
 		Completed,
+		Rejected,
+		Vetoed,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Debug, Clone, PartialEq)]
+	pub enum WinnerOutcome<ProposalIndex> {
+		/// The leading proposal reached the pass threshold. [proposal_index, score]
+		Winner(ProposalIndex, u128),
+		/// The leading proposal didn't reach the pass threshold. [proposal_index, score]
+		NoPass(ProposalIndex, u128),
+		/// No proposal received votes
+		NoVotes,
+		/// The leading proposal didn't reach MinQuorum voters. [proposal_index, score]
+		NoQuorum(ProposalIndex, u128),
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Voters registered at genesis, together with their registration fee.
+		pub voters: Vec<(AccountIdOf<T>, BalanceOf<T>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { voters: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (voter_id, fee) in &self.voters {
+				assert!(!Pallet::<T>::is_voter_registered(voter_id), "Voter registered twice");
+				let initial_balance = Pallet::<T>::initial_voter_balance(*fee)
+					.expect("Genesis voter fees must be between 1 and 100");
+				let _ = T::Currency::deposit_creating(voter_id, initial_balance);
+				RegisteredVoters::<T>::insert(voter_id, true);
+				RegisteredAt::<T>::insert(voter_id, T::BlockNumber::default());
+			}
+		}
 	}
 
 	#[pallet::event]
@@ -93,28 +613,135 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// New voter registered. [who]
 		VoterRegistered { voter_id: AccountIdOf<T>, initial_balance: BalanceOf<T> },
-		/// New proposal created. [proposal_index, text, end_block]
+		/// Voter balance initialized at registration. [who, amount]
+		BalanceInitialized { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Registration fee taken from the initial balance. [who, amount]
+		RegistrationFeeCollected { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// New proposal created. [proposal_index, text, end_block, created_at, metadata]
 		NewProposalCreated {
-			proposal_index: ProposalIndex,
+			proposal_index: ProposalIndexOf<T>,
 			text: T::Hash,
 			end_block: T::BlockNumber,
+			created_at: T::BlockNumber,
+			metadata: Option<BoundedVec<u8, T::MaxMetadataLen>>,
 		},
 		/// Reserved tokens for a proposal. [who, amount]
 		TokensReserved { who: AccountIdOf<T>, amount: BalanceOf<T> },
-		/// Proposal started. [proposal_index]
-		ProposalStarted { proposal_index: ProposalIndex },
+		/// Proposal started, voting closes at end_block. [proposal_index, end_block]
+		ProposalStarted { proposal_index: ProposalIndexOf<T>, end_block: T::BlockNumber },
 		/// Voted for a proposal. [proposal_index, vote]
-		ProposalVoted { proposal_index: ProposalIndex, vote: Vote },
-		/// Proposals Voted
-		ProposalsVoted { proposals: Vec<ProposalIndex> },
+		ProposalVoted { proposal_index: ProposalIndexOf<T>, vote: Vote },
+		/// Proposals voted by an atomic batch, every vote was applied. [succeeded]
+		BatchVoteOutcome { succeeded: Vec<ProposalIndexOf<T>> },
 		/// Unreserve tokens. [who, amount, updated_balance]
 		TokensUnreserved {
 			who: AccountIdOf<T>,
 			amount: BalanceOf<T>,
 			updated_balance: BalanceOf<T>,
 		},
-		/// Voting ended.[proposal_index]
-		VotingEnded { winner: ProposalIndex },
+		/// Voting ended with a winning proposal. [proposal_index]
+		VotingEnded { winner: Option<ProposalIndexOf<T>> },
+		/// Reserved tokens for another voter. [sponsor, beneficiary, amount]
+		TokensReservedFor {
+			sponsor: AccountIdOf<T>,
+			beneficiary: AccountIdOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Start fee returned to the account that paid it. [proposal_index, amount]
+		StartFeeRefunded { proposal_index: ProposalIndexOf<T>, amount: BalanceOf<T> },
+		/// Yield credited to the reserved balance. [who, amount]
+		YieldAccrued { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Proposal result invalidated and voting reopened. [proposal_index, end_block]
+		ResultInvalidated { proposal_index: ProposalIndexOf<T>, end_block: T::BlockNumber },
+		/// End blocks of the pending proposals recomputed. [count]
+		EndBlocksRecomputed { count: u32 },
+		/// Tokens locked by a conviction vote. [who, amount, until]
+		TokensLocked { who: AccountIdOf<T>, amount: BalanceOf<T>, until: T::BlockNumber },
+		/// Conviction lock released. [who, amount]
+		TokensUnlocked { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Proposal currently leading the vote. [proposal_index, score]
+		CurrentWinner { proposal_index: ProposalIndexOf<T>, score: u128 },
+		/// Proposal moved to the drafts. [proposal_index]
+		ProposalArchived { proposal_index: ProposalIndexOf<T> },
+		/// Draft moved back to the proposals. [proposal_index]
+		ProposalRestored { proposal_index: ProposalIndexOf<T> },
+		/// Committed tokens unreserved once the proposal completed. [proposal_index, who, amount]
+		VotesRefunded {
+			proposal_index: ProposalIndexOf<T>,
+			who: AccountIdOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Voting activity halted.
+		PalletPaused,
+		/// Voting activity resumed.
+		PalletResumed,
+		/// Options of a proposal set by its proposer. [proposal_index, count]
+		ProposalOptionsSet { proposal_index: ProposalIndexOf<T>, count: u32 },
+		/// Voted for an option of a proposal. [proposal_index, option_index]
+		OptionVoted { proposal_index: ProposalIndexOf<T>, option_index: OptionIndex },
+		/// Reserved tokens unreserved without any penalty. [who, amount]
+		TokensRefunded { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Proposal creation fee paid. [who, amount]
+		ProposalFeePaid { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Proposal deposit given back to the proposer. [proposal_index, amount]
+		ProposalDepositReturned { proposal_index: ProposalIndexOf<T>, amount: BalanceOf<T> },
+		/// Proposal deposit slashed. [proposal_index, amount]
+		ProposalDepositSlashed { proposal_index: ProposalIndexOf<T>, amount: BalanceOf<T> },
+		/// Voting ended without a clear margin over the runner-up. [leader, runner_up]
+		InconclusiveResult { leader: ProposalIndexOf<T>, runner_up: ProposalIndexOf<T> },
+		/// Voting ended with a leader that didn't reach MinQuorum voters. [proposal_index]
+		VotingEndedNoQuorum { proposal_index: ProposalIndexOf<T> },
+		/// Winner reward minted to the proposer. [who, amount]
+		ProposerRewarded { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Final (proposal_index, ayes, nays, abstains) of every proposal when voting ends.
+		ProposalResults {
+			results:
+				BoundedVec<(ProposalIndexOf<T>, BalanceOf<T>, BalanceOf<T>, u32), T::MaxProposals>,
+		},
+		/// Proposal removed because it wasn't started in time. [proposal_index]
+		ProposalExpiredUnstarted { proposal_index: ProposalIndexOf<T> },
+		/// Reward pool funded. [who, amount]
+		RewardPoolFunded { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Winner proposer rewarded from the pool. [proposal_index, who, amount]
+		WinnerRewarded {
+			proposal_index: ProposalIndexOf<T>,
+			who: AccountIdOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Reward skipped because the pool is underfunded. [proposal_index]
+		RewardPoolEmpty { proposal_index: ProposalIndexOf<T> },
+		/// Proposal cancelled by its proposer before starting. [proposal_index]
+		ProposalCancelled { proposal_index: ProposalIndexOf<T> },
+		/// Voter removed by root. [voter_id]
+		VoterUnregistered { voter_id: AccountIdOf<T> },
+		/// Vote replaced on a proposal. [proposal_index, vote]
+		VoteChanged { proposal_index: ProposalIndexOf<T>, vote: Vote },
+		/// Voting power delegated. [who, to]
+		Delegated { who: AccountIdOf<T>, to: AccountIdOf<T> },
+		/// Delegation removed. [who]
+		Undelegated { who: AccountIdOf<T> },
+		/// Hidden vote committed to a proposal. [proposal_index]
+		VoteCommitted { proposal_index: ProposalIndexOf<T> },
+		/// Committed vote revealed and counted. [proposal_index, vote]
+		VoteRevealed { proposal_index: ProposalIndexOf<T>, vote: Vote },
+		/// Proposal rejected by root. [proposal_index]
+		ProposalRejected { proposal_index: ProposalIndexOf<T> },
+		/// Batch of voters registered. [count]
+		VotersRegistered { count: u32 },
+		/// Proposal completed with more nays than ayes. [proposal_index]
+		ProposalVetoed { proposal_index: ProposalIndexOf<T> },
+		/// Proposal completed with its final tally. [proposal_index, ayes, nays]
+		ProposalCompleted {
+			proposal_index: ProposalIndexOf<T>,
+			ayes: BalanceOf<T>,
+			nays: BalanceOf<T>,
+		},
+		/// Voting ended with no proposal holding positive net votes.
+		VotingEndedWithoutWinner,
+		/// Late vote not counted, the proposal was completed instead. [proposal_index, who]
+		LateVoteIgnored { proposal_index: ProposalIndexOf<T>, who: T::AccountId },
+		/// Voting period of a proposal extended by root. [proposal_index, new_end_block]
+		ProposalExtended { proposal_index: ProposalIndexOf<T>, new_end_block: T::BlockNumber },
 	}
 
 	#[pallet::error]
@@ -143,8 +770,6 @@ pub mod pallet {
 		VoterAlreadyRegistered,
 		/// Invalid tokens amount to reserve
 		InvalidTokensAmountToReserve,
-		/// At least one of the proposals given is not registered or not active
-		AtLeastOneProposalNotRegisteredOrNotActive,
 		/// Invalid tokens amount to unreserve
 		InvalidTokensAmountToUnreserve,
 		/// Balance Overflow
@@ -153,6 +778,118 @@ pub mod pallet {
 		SlashFailed,
 		/// Balance addition overflow
 		AdditionOverflow,
+		/// Tokens were reserved after the proposal started
+		ReservedTooLate,
+		/// Voter must create more proposals before voting
+		MustProposeFirst,
+		/// Proposal is not completed
+		ProposalNotCompleted,
+		/// Proposal already completed, vetoed or rejected
+		ProposalAlreadyClosed,
+		/// Proposers can't vote their own proposals
+		CannotVoteOwnProposal,
+		/// More voters than MaxBatchSize in a single batch
+		TooManyVoters,
+		/// The same proposal appears more than once in the batch
+		DuplicateProposalInBatch,
+		/// The voting period of the proposal is not over yet
+		VotingStillActive,
+		/// Vote top up is on the other side of the existing vote
+		VoteDirectionMismatch,
+		/// Vote would push the account over MaxVotesPerVoter on the proposal
+		VoteCapExceeded,
+		/// Amount to reserve is below MinReserveAmount
+		ReserveBelowMinimum,
+		/// Only Aye votes can be cast in a batch
+		VoteNotSupportedInBatch,
+		/// Voting activity is paused
+		PalletPaused,
+		/// Proposal has no option with the given index
+		InvalidOption,
+		/// More options than MaxOptions
+		TooManyOptions,
+		/// Dispute window is closed, the result is final
+		DisputeWindowClosed,
+		/// Too many lock periods for a conviction vote
+		ConvictionTooHigh,
+		/// Voter has no conviction lock
+		NoConvictionLock,
+		/// Conviction lock has not expired yet
+		LockNotExpired,
+		/// Reserved tokens are locked by a conviction vote
+		TokensLocked,
+		/// Proposer has reserved less than MinProposerReserve
+		InsufficientProposerReserve,
+		/// Only the proposer can do this
+		NotProposer,
+		/// Draft not found
+		DraftNotFound,
+		/// Vote would exceed the maximum reserve committed to the proposal
+		ProposalReserveCapReached,
+		/// Voter has voted on too many proposals
+		TooManyVotedProposals,
+		/// Proposal reached the maximum number of voters
+		ProposalVoterCapReached,
+		/// Proposal text is empty
+		EmptyProposalText,
+		/// Proposal text is longer than MaxProposalTextLen
+		ProposalTextTooLong,
+		/// Proposal metadata is longer than MaxMetadataLen
+		MetadataTooLong,
+		/// MaxProposals proposals are already stored
+		TooManyProposals,
+		/// Voting period is out of the MinVotingPeriod..=MaxVotingPeriod range
+		InvalidVotingPeriod,
+		/// Voter has not voted Aye or Nay on the proposal
+		NotVoted,
+		/// Delegation would lead back to the delegator
+		DelegationCycle,
+		/// Voter has not delegated its voting power
+		NotDelegated,
+		/// Voter delegated its voting power, so it can't vote directly
+		VotingPowerDelegated,
+		/// Revealed vote doesn't match the commitment
+		InvalidReveal,
+		/// Voter has no vote committed to the proposal
+		CommitmentNotFound,
+		/// Votes can only be revealed once the proposal ended
+		RevealTooEarly,
+		/// Only the proposer can cancel the proposal
+		NotProposalOwner,
+		/// The delegation would exceed MaxDelegators voters behind a single delegate
+		TooManyDelegators,
+		/// Tokens flowing through the delegation are committed to proposals
+		DelegatedTokensCommitted,
+		/// Voting is over, the proposal only takes reveals until it completes
+		VotingPeriodEnded,
+		/// The reveal period of the proposal is over
+		RevealPeriodEnded,
+		/// The voter committed a hidden vote on the proposal, it can only be revealed
+		CommitmentPending,
+		/// Abstentions carry no weight, so they can't be cast with conviction
+		ConvictionAbstain,
+		/// The tokens are committed to votes on proposals that haven't been settled
+		TokensCommitted,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::accrue_reserve_yield(n).saturating_add(Self::expiry_weight(n))
+		}
+
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::expire_unstarted_proposals(n, remaining_weight)
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			Self::complete_expiring_proposals(n)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: T::BlockNumber) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
 	}
 
 	#[pallet::call]
@@ -160,69 +897,151 @@ pub mod pallet {
 		/*
 			* Register a new voter
 			 * @param fee: Fee to register a new voter
+			 * @param preserve_balance: Keep the balance the account already holds instead of crediting a new one
 			* @return DispatchResult
 
 			* This function will create a new voter and will reserve 100 - fee tokens to be used as voting tokens
+			* An unregistered voter can be registered again, e.g. when migrating voters, preserving its balance
 			* To create a new user, "root user" must call this function passing the user id and the fee
 
 		*/
 		#[pallet::call_index(0)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::register_voter())]
 		pub fn register_voter(
 			origin: OriginFor<T>,
 			voter_id: AccountIdOf<T>,
 			fee: BalanceOf<T>,
+			preserve_balance: bool,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			// The balance of an already registered voter is never re-initialized
+			if Self::is_voter_registered(&voter_id) && T::RegistrationIdempotent::get() {
+				return Ok(())
+			}
 			ensure!(!Self::is_voter_registered(&voter_id), Error::<T>::VoterAlreadyRegistered);
-			ensure!(fee > 0u32.into(), Error::<T>::InsufficientFee);
-			// Create initial balance for the voter equals to 100 - fee
-			let initial_balance_without_fee: BalanceOf<T> = 100u32.into();
-			ensure!(
-				Self::checked_sub_between_balances(initial_balance_without_fee, fee).is_ok(),
-				Error::<T>::SubstractionOverflow
-			);
-
-			let initial_balance =
-				Self::checked_sub_between_balances(initial_balance_without_fee, fee)?;
-			T::Currency::make_free_balance_be(&voter_id, initial_balance);
-
-			RegisteredVoters::<T>::insert(&voter_id, true);
-			Self::deposit_event(Event::VoterRegistered { voter_id, initial_balance });
-			Ok(())
+			Self::do_register_voter(voter_id, fee, preserve_balance)
 		}
 
 		/*
 			* Create a new proposal
 			 * @param text: Proposal text
+			* @param duration: Voting period of the proposal, VotingPeriod if None
+			* @param metadata: Off-chain metadata of the proposal (IPFS CID, URL...), if any
 			* @return DispatchResult
 
 			* This function will create a new proposal.
 			* The only requirement is that the user must be a registered voter.
 			* To create a new proposal, a registered voter must call this function passing the proposal text.
+			* The text is stored bounded by MaxProposalTextLen, and its hash is kept in the proposal.
+			* A custom duration must be between MinVotingPeriod and MaxVotingPeriod
+			* The metadata is stored bounded by MaxMetadataLen
+			* The proposal gets the next index, failing with StorageOverflow once they are exhausted
+			* The proposer pays ProposalCreationFee, if any, and must have enough free balance for it
+			* ProposalDeposit is reserved too, it's given back if the proposal reaches quorum and slashed if it doesn't or if it's rejected
 
 		*/
 		#[pallet::call_index(1)]
-		#[pallet::weight(0)]
-		pub fn create_proposal(origin: OriginFor<T>, text: T::Hash) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::create_proposal())]
+		pub fn create_proposal(
+			origin: OriginFor<T>,
+			text: Vec<u8>,
+			duration: Option<T::BlockNumber>,
+			metadata: Option<Vec<u8>>,
+		) -> DispatchResult {
 			let proposer = ensure_signed(origin)?;
+			ensure!(!Self::is_paused(), Error::<T>::PalletPaused);
 			ensure!(Self::is_voter_registered(&proposer), Error::<T>::NotRegisteredVoter);
 
-			let proposal_index = Proposals::<T>::count() + 1;
+			let text_hash = T::Hashing::hash_of(&text);
+			ensure!(
+				!T::RejectEmptyText::get() || text_hash.as_ref() != &EMPTY_TEXT_HASH[..],
+				Error::<T>::EmptyProposalText
+			);
+			let bounded_text: BoundedVec<u8, T::MaxProposalTextLen> =
+				text.try_into().map_err(|_| Error::<T>::ProposalTextTooLong)?;
+			let metadata: Option<BoundedVec<u8, T::MaxMetadataLen>> = metadata
+				.map(|metadata| metadata.try_into().map_err(|_| Error::<T>::MetadataTooLong))
+				.transpose()?;
+			// Removed proposals free their slot
+			ensure!(Proposals::<T>::count() < T::MaxProposals::get(), Error::<T>::TooManyProposals);
+			let voting_period = match duration {
+				Some(duration) => {
+					ensure!(
+						duration >= T::MinVotingPeriod::get() &&
+							duration <= T::MaxVotingPeriod::get(),
+						Error::<T>::InvalidVotingPeriod
+					);
+					duration
+				},
+				None => T::VotingPeriod::get(),
+			};
+
+			// Indexes start at 1 and are never reused, even if older proposals were removed
+			let proposal_index = NextProposalIndex::<T>::get().max(1u32.into());
+			let next_proposal_index =
+				proposal_index.checked_add(&1u32.into()).ok_or(Error::<T>::StorageOverflow)?;
+
+			let creation_fee = T::ProposalCreationFee::get();
+			if creation_fee > 0u32.into() {
+				ensure!(
+					Self::can_pay_keeping_alive(&proposer, creation_fee),
+					Error::<T>::NotEnoughBalance
+				);
+				let fee = T::Currency::withdraw(
+					&proposer,
+					creation_fee,
+					WithdrawReasons::FEE,
+					ExistenceRequirement::KeepAlive,
+				)?;
+				T::FeeHandler::on_unbalanced(fee);
+				Self::deposit_event(Event::ProposalFeePaid {
+					who: proposer.clone(),
+					amount: creation_fee,
+				});
+			}
+			// Kept apart from the reserved voting tokens until the proposal is settled
+			let deposit = T::ProposalDeposit::get();
+			if deposit > 0u32.into() {
+				T::Currency::reserve(&proposer, deposit)
+					.map_err(|_| Error::<T>::NotEnoughBalance)?;
+				HeldDeposits::<T>::mutate(&proposer, |held| *held = held.saturating_add(deposit));
+			}
 
-			let end_block = <frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get();
+			let created_at = <frame_system::Pallet<T>>::block_number();
+			let end_block = created_at + voting_period;
 
 			let proposal = Proposal {
 				proposal_index,
-				text,
-				proposer,
+				text: text_hash,
+				proposer: proposer.clone(),
 				end_block,
+				start_block: 0u32.into(),
+				start_fee: 0u32.into(),
+				created_at,
+				completed_at: None,
 				status: ProposalStatus::NotStarted,
+				options: BoundedVec::default(),
+				metadata: metadata.clone(),
+				deposit,
 			};
 
 			Proposals::<T>::insert(proposal_index, proposal);
-			Self::deposit_event(Event::NewProposalCreated { proposal_index, text, end_block });
+			ProposalTexts::<T>::insert(proposal_index, bounded_text);
+			if duration.is_some() {
+				CustomVotingPeriods::<T>::insert(proposal_index, voting_period);
+			}
+			NextProposalIndex::<T>::put(next_proposal_index);
+			ProposerIndex::<T>::try_mutate(&proposer, |indexes| indexes.try_push(proposal_index))
+				.map_err(|_| Error::<T>::TooManyProposals)?;
+			ProposalsByProposer::<T>::mutate(&proposer, |count| *count = count.saturating_add(1));
+			Self::deposit_event(Event::NewProposalCreated {
+				proposal_index,
+				text: text_hash,
+				end_block,
+				created_at,
+				metadata,
+			});
 			Ok(())
 		}
 
@@ -235,42 +1054,72 @@ pub mod pallet {
 			* This function will start a proposal.
 			* The only requirement is that the user must be a registered voter
 			* Ii will check if the proposal exists and if the proposal is not already started
+			* The voting period of the proposal starts counting from this block
+			* The user must have at least MinProposerReserve tokens reserved
 			* To start a proposal, a registered voter must call this function passing the proposal index and the fee needed to start the proposal
 		*/
 		#[pallet::call_index(2)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::start_proposal())]
 		pub fn start_proposal(
 			origin: OriginFor<T>,
-			proposal_index: ProposalIndex,
+			proposal_index: ProposalIndexOf<T>,
 			fee: BalanceOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_paused(), Error::<T>::PalletPaused);
 
 			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
 			ensure!(Self::get_proposal(proposal_index).is_some(), Error::<T>::ProposalNotFound);
-			let balance = Self::get_voter_balance(&who);
-			ensure!(balance >= fee, Error::<T>::NotEnoughBalance);
+			ensure!(Self::can_pay_keeping_alive(&who, fee), Error::<T>::NotEnoughBalance);
 			ensure!(
-				Self::get_proposal_status(proposal_index) == ProposalStatus::NotStarted,
+				Self::get_proposal_status(proposal_index)? == ProposalStatus::NotStarted,
 				Error::<T>::ProposalAlreadyStarted
 			);
 			ensure!(fee > 0u32.into(), Error::<T>::InsufficientFee);
+			ensure!(
+				T::Currency::reserved_balance(&who) >= T::MinProposerReserve::get(),
+				Error::<T>::InsufficientProposerReserve
+			);
 
 			let proposal: Proposal<T> =
 				Self::get_proposal(proposal_index).ok_or("Proposal not found")?;
 
+			// The voting period chosen at creation only starts running now
+			let start_block = <frame_system::Pallet<T>>::block_number();
+			let end_block = start_block + proposal.end_block.saturating_sub(proposal.created_at);
 			let proposal_updated: Proposal<T> = Proposal {
 				proposal_index,
 				text: proposal.text,
 				proposer: proposal.proposer,
-				end_block: proposal.end_block,
+				end_block,
+				start_block,
+				start_fee: fee,
+				created_at: proposal.created_at,
+				completed_at: None,
 				status: ProposalStatus::InProgress,
+				options: proposal.options,
+				metadata: proposal.metadata,
+				deposit: proposal.deposit,
 			};
 
 			Proposals::<T>::mutate(proposal_index, |p| *p = Some(proposal_updated));
-
-			T::Currency::make_free_balance_be(&who, balance - fee);
-			Self::deposit_event(Event::ProposalStarted { proposal_index });
+			Self::schedule_expiry(proposal_index, end_block);
+
+			if T::RefundStartFee::get() {
+				// Held until the proposal is settled, so the refund doesn't mint new tokens
+				T::Currency::reserve(&who, fee).map_err(|_| Error::<T>::NotEnoughBalance)?;
+				HeldDeposits::<T>::mutate(&who, |held| *held = held.saturating_add(fee));
+				HeldStartFees::<T>::insert(proposal_index, (who, fee));
+			} else {
+				let fee = T::Currency::withdraw(
+					&who,
+					fee,
+					WithdrawReasons::FEE,
+					ExistenceRequirement::KeepAlive,
+				)?;
+				T::FeeHandler::on_unbalanced(fee);
+			}
+			Self::deposit_event(Event::ProposalStarted { proposal_index, end_block });
 
 			Ok(())
 		}
@@ -283,22 +1132,27 @@ pub mod pallet {
 			* This function will reserve tokens.
 			* The requirements are:
 				- The user must be a registered voter
-				- The amount of tokens to reserve must be greater than 0
+				- The amount of tokens to reserve must be greater than 0 and at least MinReserveAmount
 				- The user must have enough balance to reserve the tokens
 			* To reserve tokens, a registered voter must call this function passing the amount of tokens to reserve
 		*/
 		#[pallet::call_index(3)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::reserve_tokens())]
 		pub fn reserve_tokens(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
 			ensure!(amount > 0u32.into(), Error::<T>::InvalidTokensAmountToReserve);
+			// Keeps dust reservations from voting on many proposals for almost nothing
+			ensure!(amount >= T::MinReserveAmount::get(), Error::<T>::ReserveBelowMinimum);
 
-			let voter_balance = Self::get_voter_balance(&who);
+			let voter_balance = Self::get_free_voting_balance(&who);
 			ensure!(voter_balance >= amount, Error::<T>::NotEnoughBalance);
 
 			// Reserve tokens
 			T::Currency::reserve(&who, amount)?;
+			if !ReservedAt::<T>::contains_key(&who) {
+				ReservedAt::<T>::insert(&who, <frame_system::Pallet<T>>::block_number());
+			}
 			Self::deposit_event(Event::TokensReserved { who, amount });
 
 			Ok(())
@@ -307,67 +1161,138 @@ pub mod pallet {
 		/*
 			* Vote a proposal
 			 * @param proposal_index: Proposal index
+			* @param tokens_to_use: Amount of reserved tokens to spend on the vote
 			* @param vote: Vote
-			* @return DispatchResult
+			* @param conviction: Index in CONVICTION_MULTIPLIERS of the multiplier of the vote
+			* @return DispatchResultWithPostInfo
 
 			* This function will vote a proposal.
+			* With a conviction, the quadratic weight is multiplied by CONVICTION_MULTIPLIERS[conviction]
+			* and the tokens used can't be unreserved nor refunded for multiplier * LockPeriodLength blocks
+			* The weight charged covers a full delegation tree and completing an expired proposal, the unused part is refunded
 			* The requirements are:
 				- The user must be a registered voter
 				- The proposal must be registered
 				- The proposal must be active
 				- The voting period must be still live
-			* To vote a proposal, a registered voter must call this function passing the proposal index and the vote
+				- The user must not have committed a hidden vote on the proposal
+				- The user must have enough reserved tokens left to use
+				- The conviction must be in CONVICTION_MULTIPLIERS
+			* To vote a proposal, a registered voter must call this function passing the proposal index, the tokens to use and the vote
 		*/
 		#[pallet::call_index(4)]
-		#[pallet::weight(0)]
+		#[pallet::weight(
+			T::WeightInfo::vote_proposal(T::MaxDelegators::get()).max(Pallet::<T>::late_vote_weight())
+		)]
 		pub fn vote_proposal(
 			origin: OriginFor<T>,
-			proposal_index: u32,
+			proposal_index: ProposalIndexOf<T>,
+			tokens_to_use: BalanceOf<T>,
 			vote: Vote,
-		) -> DispatchResult {
+			conviction: u8,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
-			ensure!(Self::is_proposal_registered(proposal_index), Error::<T>::ProposalNotFound);
-			ensure!(Self::is_proposal_active(proposal_index), Error::<T>::ProposalNotActive);
-
-			// Check if the VotingEnded is still live
-			let current_block = <frame_system::Pallet<T>>::block_number();
-			let proposal_end_block = Self::get_proposal_end_block(proposal_index);
-
-			if proposal_end_block <= current_block {
-				Self::update_proposal_status_to_completed(proposal_index);
-
-				let winner = Self::get_winner();
-				Self::deposit_event(Event::VotingEnded { winner });
-				return Ok(());
+			if !Self::ensure_can_cast_vote(&who, proposal_index)? {
+				Self::deposit_voting_ended(Self::get_winner());
+				return Ok(Self::late_vote_post_info(proposal_index))
 			}
-
-			// Check if the user has token reserved
-			let reserved_tokens = T::Currency::reserved_balance(&who);
+			let multiplier = CONVICTION_MULTIPLIERS
+				.get(conviction as usize)
+				.copied()
+				.ok_or(Error::<T>::ConvictionTooHigh)?;
+
+			// Check if the user has token reserved, counting the ones delegated to them
+			let reserved_tokens =
+				Self::get_votable_reserve(&who).saturating_add(Self::get_delegated_reserve(&who));
 			ensure!(reserved_tokens > 0u32.into(), Error::<T>::NotEnoughReservedTokens);
 
 			match vote {
-				Vote::Aye => {
+				Vote::Aye | Vote::Nay => {
+					ensure!(Self::can_vote(proposal_index, &who), Error::<T>::VoterAlreadyVoted);
+					// A voter can't back both sides of the same proposal
+					let (votes, voted_other_side) = match vote {
+						Vote::Aye => (
+							Self::get_aye_votes_balance(proposal_index, &who),
+							NayVotes::<T>::contains_key(proposal_index, &who),
+						),
+						_ => (
+							Self::get_nay_votes_balance(proposal_index, &who),
+							AyeVotes::<T>::contains_key(proposal_index, &who),
+						),
+					};
 					ensure!(
-						!Self::voter_has_voted(proposal_index, &who),
+						!voted_other_side && !AbstainVotes::<T>::contains_key(proposal_index, &who),
 						Error::<T>::VoterAlreadyVoted
 					);
-					// Quadratic voting logic
-					let aye_votes = Self::get_aye_votes_balance(proposal_index, &who);
-					let new_aye_votes = aye_votes + reserved_tokens.integer_sqrt();
-					AyeVotes::<T>::set(proposal_index, &who, new_aye_votes);
-
-					// Finally, update the total of tokens available for the voter
-					let voter_balance = Self::get_voter_balance(&who);
-					T::Currency::make_free_balance_be(&who, voter_balance);
+					// Tokens spent on earlier votes can't be used again
+					ensure!(
+						tokens_to_use > 0u32.into() &&
+							Self::get_available_reserve(&who) >= tokens_to_use,
+						Error::<T>::NotEnoughReservedTokens
+					);
+					let committed_reserve = ProposalCommittedReserve::<T>::get(proposal_index)
+						.saturating_add(tokens_to_use);
+					ensure!(
+						committed_reserve <= T::MaxProposalReserve::get(),
+						Error::<T>::ProposalReserveCapReached
+					);
+					// Quadratic voting logic, boosted by the conviction
+					let weight = Self::repeat_vote_weight(
+						proposal_index,
+						&who,
+						Self::vote_weight(&who, proposal_index, tokens_to_use)
+							.saturating_mul(multiplier.into()),
+					);
+					let new_votes = Self::checked_add_between_balances(votes, weight)?;
+					Self::ensure_within_vote_cap(new_votes)?;
+					Self::count_proposal_voter(proposal_index, &who)?;
+					log::debug!(
+						target: LOG_TARGET,
+						"vote on proposal {:?}: committed {:?}, weight {:?}",
+						proposal_index,
+						tokens_to_use,
+						weight
+					);
+					log::trace!(
+						target: LOG_TARGET,
+						"voter tally on proposal {:?}: {:?} -> {:?}",
+						proposal_index,
+						votes,
+						new_votes
+					);
+					match vote {
+						Vote::Aye => AyeVotes::<T>::set(proposal_index, &who, new_votes),
+						_ => NayVotes::<T>::set(proposal_index, &who, new_votes),
+					}
+					Self::add_to_tally(proposal_index, &vote, weight);
+					VoteCounts::<T>::mutate(proposal_index, &who, |count| {
+						*count = count.saturating_add(1)
+					});
+					Self::record_voted_proposal(&who, proposal_index)?;
+					CommittedTokens::<T>::mutate(&who, proposal_index, |committed| {
+						*committed = committed.saturating_add(tokens_to_use)
+					});
+					ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+					if conviction > 0 {
+						Self::lock_reserve(&who, tokens_to_use, multiplier);
+					}
 
 					// Don't expose the voter to the public (to guarantee privacy)
 					Self::deposit_event(Event::ProposalVoted { proposal_index, vote });
 				},
-				_ => {},
+				Vote::Abstain => {
+					// Abstaining is final, even when repeat votes are allowed
+					ensure!(
+						!Self::voter_has_voted(proposal_index, &who),
+						Error::<T>::VoterAlreadyVoted
+					);
+					Self::count_proposal_voter(proposal_index, &who)?;
+					AbstainVotes::<T>::insert(proposal_index, &who, ());
+					Self::deposit_event(Event::ProposalVoted { proposal_index, vote });
+				},
 			};
 
-			Ok(())
+			Ok(Some(T::WeightInfo::vote_proposal(Self::delegator_count(&who))).into())
 		}
 
 		/*
@@ -379,32 +1304,61 @@ pub mod pallet {
 			* The requirements are:
 				- The user must be a registered voter
 				- The amount of tokens to unreserve must be greater than 0
+				- The tokens to unreserve can't be locked by a conviction vote
+				- The tokens to unreserve can't be committed to votes
 				- The user must have enough reserved tokens to unreserve the tokens
 			* To unreserve tokens, a registered voter must call this function passing the amount of tokens to unreserve
 		*/
 		#[pallet::call_index(5)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::unreserve_tokens())]
 		pub fn unreserve_tokens(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
 			ensure!(amount > 0u32.into(), Error::<T>::InvalidTokensAmountToUnreserve);
-			let reserved_tokens = T::Currency::reserved_balance(&who);
+			// A delegate may already be voting with these tokens
+			ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::VotingPowerDelegated);
+			// Tokens reserved on behalf of other voters and proposal deposits can't be unreserved
+			let reserved_tokens = T::Currency::reserved_balance(&who)
+				.saturating_sub(SponsoredOut::<T>::get(&who))
+				.saturating_sub(HeldDeposits::<T>::get(&who));
 			ensure!(reserved_tokens >= amount, Error::<T>::NotEnoughReservedTokens);
+			ensure!(
+				reserved_tokens.saturating_sub(Self::locked_reserve(&who)) >= amount,
+				Error::<T>::TokensLocked
+			);
+			// Neither can the tokens backing votes on proposals still in progress
+			ensure!(
+				reserved_tokens.saturating_sub(Self::get_committed_tokens(&who)) >= amount,
+				Error::<T>::TokensCommitted
+			);
+
+			// The slash can't reap the voter either
+			ensure!(
+				T::Currency::total_balance(&who).saturating_sub(amount / 2u32.into()) >=
+					T::Currency::minimum_balance(),
+				Error::<T>::NotEnoughBalance
+			);
 
 			// Update the reserved tokens
 			T::Currency::unreserve(&who, amount);
-			// The "punishment" for unreserve tokens is that the voter balance will be reduced by the half of the unreserved tokens
+			// The "punishment" for unreserve tokens is that the voter balance will be reduced by
+			// the half of the unreserved tokens
 			ensure!(
 				Self::checked_div_between_balances(amount, 2u32.into()).is_ok(),
 				Error::<T>::SlashFailed
 			);
-			T::Currency::slash(
+			let (slashed, _) = T::Currency::slash(
 				&who,
 				Self::checked_div_between_balances(amount, 2u32.into())
 					.expect("Slash already checked; QEP"),
 			);
+			T::FeeHandler::on_unbalanced(slashed);
+			// Once everything is unreserved, the next reservation starts a new commitment
+			if T::Currency::reserved_balance(&who) == 0u32.into() {
+				ReservedAt::<T>::remove(&who);
+			}
 			// Update the voter balance
-			let updated_balance = Self::get_voter_balance(&who);
+			let updated_balance = Self::get_free_voting_balance(&who);
 
 			Self::deposit_event(Event::TokensUnreserved { who, amount, updated_balance });
 
@@ -417,6 +1371,10 @@ pub mod pallet {
 			* @return DispatchResult
 
 			* This function will vote multiple proposals.
+			* The batch is atomic: if one of the requirements or one of the votes fails, nothing is voted
+			* An entry whose voting period is over completes its proposal and is skipped, the rest of the batch is still voted
+			* Each vote commits its tokens, so the later votes of the batch can't spend them again
+			* Only Aye votes can be cast in a batch
 			* The requirements are:
 				- The user must be a registered voter
 				- The proposals must be registered and active
@@ -424,150 +1382,2066 @@ pub mod pallet {
 			* To vote multiple proposals, a registered voter must call this function passing the proposals to vote, the amount of tokens to vote and the vote
 		*/
 		#[pallet::call_index(6)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::vote_multiple_proposals(proposals.len() as u32))]
 		pub fn vote_multiple_proposals(
 			origin: OriginFor<T>,
-			proposals: Vec<(ProposalIndex, BalanceOf<T>, Vote)>,
+			proposals: Vec<(ProposalIndexOf<T>, BalanceOf<T>, Vote)>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_paused(), Error::<T>::PalletPaused);
 			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
 
-			// Check if the proposals are registered and active
-			let are_proposals_registered_and_active = proposals.iter().all(|proposal| {
-				let proposal_index = proposal.0;
-				Self::is_proposal_registered(proposal_index)
-					&& Self::is_proposal_active(proposal_index)
-			});
+			// Once the voting period of a proposal is over, its entry completes it and is skipped
+			let mut live_proposals = Vec::new();
+			for proposal in proposals.iter() {
+				if Self::ensure_can_vote(&who, proposal.0)? {
+					live_proposals.push(proposal.clone());
+				}
+			}
+			if live_proposals.len() < proposals.len() {
+				Self::deposit_voting_ended(Self::get_winner());
+			}
+			if live_proposals.is_empty() {
+				return Ok(())
+			}
+			let proposals = live_proposals;
+			ensure!(Self::has_proposed_enough(&who), Error::<T>::MustProposeFirst);
+			ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::VotingPowerDelegated);
+
+			// The has-voted check below can't catch the same proposal twice within the batch
+			let mut proposal_indexes: Vec<ProposalIndexOf<T>> =
+				proposals.iter().map(|proposal| proposal.0).collect();
+			proposal_indexes.sort();
+			proposal_indexes.dedup();
 			ensure!(
-				are_proposals_registered_and_active,
-				Error::<T>::AtLeastOneProposalNotRegisteredOrNotActive
+				proposal_indexes.len() == proposals.len(),
+				Error::<T>::DuplicateProposalInBatch
 			);
 
 			// Check if the user has token reserved
-			let reserved_tokens = T::Currency::reserved_balance(&who);
-			let total_tokens_to_use = proposals.iter().fold(0u32.into(), |acc, proposal| {
-				acc + proposal.1
-			});
+			let reserved_tokens = Self::get_available_reserve(&who);
+			let total_tokens_to_use: BalanceOf<T> = proposals
+				.iter()
+				.fold(0u32.into(), |acc, proposal| acc.saturating_add(proposal.1));
 			ensure!(reserved_tokens >= total_tokens_to_use, Error::<T>::NotEnoughReservedTokens);
 
 			// Check if the user has already vote for any of the proposals
 			let has_voted_for_any_proposal = proposals.iter().any(|proposal| {
 				let proposal_index = proposal.0;
-				Self::voter_has_voted(proposal_index, &who)
+				!Self::can_vote(proposal_index, &who)
 			});
 			ensure!(!has_voted_for_any_proposal, Error::<T>::VoterAlreadyVoted);
 
-			let current_block = <frame_system::Pallet<T>>::block_number();
-			let proposals_are_still_active = proposals.iter().all(|proposal| {
-				let proposal_index = proposal.0;
-				let proposal_end_block = Self::get_proposal_end_block(proposal_index);
-				current_block < proposal_end_block
-			});
-			// If the proposals are not active anymore, we need to update the status of the proposals to completed
-			if !proposals_are_still_active {
-				let winner = Self::get_winner();
-				Self::update_proposal_status_to_completed(winner);
-				Self::deposit_event(Event::VotingEnded { winner });
-
-				return Ok(());
-			}
-
-			for proposal in proposals.clone() {
-				let (proposal_index, tokens_to_use, vote) = proposal;
-
-				match vote {
-					Vote::Aye => {
-						let aye_votes = Self::get_aye_votes_balance(proposal_index, &who);
-						// Quadratic voting logic
-						ensure!(
-							Self::checked_add_between_balances(
-								aye_votes,
-								tokens_to_use.integer_sqrt()
-							)
-							.is_ok(),
-							Error::<T>::AdditionOverflow
-						);
-						let new_aye_votes = Self::checked_add_between_balances(
-							aye_votes,
-							tokens_to_use.integer_sqrt(),
-						)
-						.expect("Addition already checked; QEP");
-
-						AyeVotes::<T>::set(proposal_index, &who, new_aye_votes);
-
-						// Finally, update the total of tokens available for the voter
-						let voter_balance = Self::get_voter_balance(&who);
-						T::Currency::make_free_balance_be(&who, voter_balance);
-
-						// Don't expose the voter to the public (to guarantee privacy)
-						Self::deposit_event(Event::ProposalsVoted {
-							proposals: proposals.iter().map(|proposal| proposal.0).collect(),
-						});
-					},
-					_ => {},
-				};
+			for proposal in proposals.iter() {
+				Self::ensure_reserved_before_start(&who, proposal.0)?;
+				Self::ensure_not_own_proposal(&who, proposal.0)?;
+				Self::ensure_no_pending_commitment(&who, proposal.0)?;
 			}
 
+			// A failing vote rolls back the ones applied before it
+			let succeeded: Vec<ProposalIndexOf<T>> =
+				proposals.iter().map(|proposal| proposal.0).collect();
+			frame_support::storage::with_storage_layer(|| -> DispatchResult {
+				for (proposal_index, tokens_to_use, vote) in proposals {
+					Self::vote_in_batch(&who, proposal_index, tokens_to_use, vote)?;
+				}
+				Ok(())
+			})?;
+
+			// Don't expose the voter to the public (to guarantee privacy)
+			Self::deposit_event(Event::BatchVoteOutcome { succeeded });
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		pub fn is_voter_registered(who: &T::AccountId) -> bool {
-			RegisteredVoters::<T>::contains_key(who)
+		/*
+			* Invalidate a proposal result
+			 * @param proposal_index: Proposal index
+			* @return DispatchResult
+
+			* This function will discard the result of a completed proposal and reopen the voting.
+			* The requirements are:
+				- The proposal must be completed or vetoed
+				- The current block must be within the dispute window (end_block + DisputeWindow)
+			* To invalidate a result, the "root user" must call this function passing the proposal index
+		*/
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::invalidate_result())]
+		pub fn invalidate_result(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(
+				matches!(proposal.status, ProposalStatus::Completed | ProposalStatus::Vetoed),
+				Error::<T>::ProposalNotCompleted
+			);
+
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				current_block <= proposal.end_block + T::DisputeWindow::get(),
+				Error::<T>::DisputeWindowClosed
+			);
+
+			// Reopen the proposal for a whole new voting period
+			let end_block = current_block + T::VotingPeriod::get();
+			let proposal_updated: Proposal<T> = Proposal {
+				proposal_index,
+				text: proposal.text,
+				proposer: proposal.proposer,
+				end_block,
+				start_block: proposal.start_block,
+				start_fee: proposal.start_fee,
+				created_at: proposal.created_at,
+				completed_at: None,
+				status: ProposalStatus::InProgress,
+				options: proposal.options,
+				metadata: proposal.metadata,
+				deposit: proposal.deposit,
+			};
+			Proposals::<T>::insert(proposal_index, proposal_updated);
+			Self::schedule_expiry(proposal_index, end_block);
+
+			Self::deposit_event(Event::ResultInvalidated { proposal_index, end_block });
+			Ok(())
 		}
-		pub fn is_proposal_registered(proposal_index: ProposalIndex) -> bool {
-			Proposals::<T>::contains_key(proposal_index)
+
+		/*
+			* Reserve tokens on behalf of another voter
+			 * @param beneficiary: Voter that can vote with the reserved tokens
+			* @param amount: Amount of tokens to reserve
+			* @return DispatchResult
+
+			* This function will reserve tokens from the sponsor's balance and credit them to the beneficiary.
+			* The requirements are:
+				- Both the sponsor and the beneficiary must be registered voters
+				- The amount of tokens to reserve must be greater than 0
+				- The sponsor must have enough balance to reserve the tokens
+			* The tokens stay reserved on the sponsor's account, so they go back to the sponsor and not to the beneficiary
+		*/
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::reserve_for())]
+		pub fn reserve_for(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdOf<T>,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(Self::is_voter_registered(&sponsor), Error::<T>::NotRegisteredVoter);
+			ensure!(Self::is_voter_registered(&beneficiary), Error::<T>::NotRegisteredVoter);
+			ensure!(amount > 0u32.into(), Error::<T>::InvalidTokensAmountToReserve);
+
+			let sponsor_balance = Self::get_free_voting_balance(&sponsor);
+			ensure!(sponsor_balance >= amount, Error::<T>::NotEnoughBalance);
+
+			T::Currency::reserve(&sponsor, amount)?;
+			Sponsorships::<T>::mutate(&sponsor, &beneficiary, |sponsored| {
+				*sponsored = sponsored.saturating_add(amount)
+			});
+			SponsoredOut::<T>::mutate(&sponsor, |sponsored| {
+				*sponsored = sponsored.saturating_add(amount)
+			});
+			SponsoredIn::<T>::mutate(&beneficiary, |sponsored| {
+				*sponsored = sponsored.saturating_add(amount)
+			});
+			if !ReservedAt::<T>::contains_key(&beneficiary) {
+				ReservedAt::<T>::insert(&beneficiary, <frame_system::Pallet<T>>::block_number());
+			}
+
+			Self::deposit_event(Event::TokensReservedFor { sponsor, beneficiary, amount });
+			Ok(())
 		}
-		pub fn is_proposal_active(proposal_index: ProposalIndex) -> bool {
-			match Proposals::<T>::get(proposal_index) {
-				Some(proposal) => {
-					proposal.status == ProposalStatus::InProgress
-						&& Self::is_proposal_registered(proposal_index)
-				},
-				_ => false,
+
+		/*
+			* Recompute the end block of the pending proposals
+			* @return DispatchResult
+
+			* This function will recompute the end block of every proposal that has not been started yet.
+			* The new end block is the creation block plus the current voting period
+			* Proposals that are already in progress or completed keep their end block
+			* Proposals created with a custom duration keep it too
+			* To recompute the end blocks, the "root user" must call this function
+		*/
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::recompute_pending_end_blocks(T::MaxProposals::get()))]
+		pub fn recompute_pending_end_blocks(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let pending_proposals: Vec<Proposal<T>> = Proposals::<T>::iter_values()
+				.filter(|proposal| {
+					proposal.status == ProposalStatus::NotStarted &&
+						!CustomVotingPeriods::<T>::contains_key(proposal.proposal_index)
+				})
+				.collect();
+			let count = pending_proposals.len() as u32;
+
+			for proposal in pending_proposals {
+				let proposal_updated: Proposal<T> = Proposal {
+					proposal_index: proposal.proposal_index,
+					text: proposal.text,
+					proposer: proposal.proposer,
+					end_block: proposal.created_at + T::VotingPeriod::get(),
+					start_block: proposal.start_block,
+					start_fee: proposal.start_fee,
+					created_at: proposal.created_at,
+					completed_at: proposal.completed_at,
+					status: proposal.status,
+					options: proposal.options,
+					metadata: proposal.metadata,
+					deposit: proposal.deposit,
+				};
+				Proposals::<T>::insert(proposal_updated.proposal_index, proposal_updated);
 			}
+
+			Self::deposit_event(Event::EndBlocksRecomputed { count });
+			Ok(())
 		}
-		pub fn get_proposal(proposal_index: ProposalIndex) -> Option<Proposal<T>> {
-			Proposals::<T>::get(proposal_index)
+
+		/*
+			* Vote a proposal with conviction
+			 * @param proposal_index: Proposal index to vote
+			* @param amount: Amount of free tokens to lock
+			* @param periods: Number of lock periods to commit to
+			* @param vote: Vote to cast
+			* @return DispatchResultWithPostInfo
+
+			* This function will vote a proposal locking free tokens instead of using the reserved ones.
+			* The quadratic weight of the locked tokens is multiplied by (1 + periods)
+			* The tokens stay locked for periods * LockPeriodLength blocks and are released with unlock_tokens
+			* The requirements are:
+				- The same ones of vote_proposal, except for the reserved tokens
+				- The vote must be Aye or Nay
+				- The periods can't be greater than MaxLockPeriods
+				- The voter must have enough free balance to lock the tokens
+				- The tokens can't take the proposal over MaxProposalReserve
+		*/
+		#[pallet::call_index(10)]
+		#[pallet::weight(
+			T::WeightInfo::vote_with_conviction().max(Pallet::<T>::late_vote_weight())
+		)]
+		pub fn vote_with_conviction(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			amount: BalanceOf<T>,
+			periods: u32,
+			vote: Vote,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if !Self::ensure_can_cast_vote(&who, proposal_index)? {
+				Self::deposit_voting_ended(Self::get_winner());
+				return Ok(Self::late_vote_post_info(proposal_index))
+			}
+			ensure!(vote != Vote::Abstain, Error::<T>::ConvictionAbstain);
+			ensure!(periods <= T::MaxLockPeriods::get(), Error::<T>::ConvictionTooHigh);
+
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			ensure!(amount > 0u32.into(), Error::<T>::InvalidTokensAmountToReserve);
+			ensure!(T::Currency::free_balance(&who) >= amount, Error::<T>::NotEnoughBalance);
+			ensure!(Self::can_vote(proposal_index, &who), Error::<T>::VoterAlreadyVoted);
+			// A voter can't back both sides of the same proposal
+			let (votes, voted_other_side) = match vote {
+				Vote::Aye => (
+					Self::get_aye_votes_balance(proposal_index, &who),
+					NayVotes::<T>::contains_key(proposal_index, &who),
+				),
+				_ => (
+					Self::get_nay_votes_balance(proposal_index, &who),
+					AyeVotes::<T>::contains_key(proposal_index, &who),
+				),
+			};
+			ensure!(
+				!voted_other_side && !AbstainVotes::<T>::contains_key(proposal_index, &who),
+				Error::<T>::VoterAlreadyVoted
+			);
+			// The locked tokens count toward the cap like the reserved ones
+			let committed_reserve =
+				ProposalCommittedReserve::<T>::get(proposal_index).saturating_add(amount);
+			ensure!(
+				committed_reserve <= T::MaxProposalReserve::get(),
+				Error::<T>::ProposalReserveCapReached
+			);
+
+			// Quadratic voting logic, boosted by the conviction
+			let weight = Self::repeat_vote_weight(
+				proposal_index,
+				&who,
+				Self::vote_weight(&who, proposal_index, amount)
+					.saturating_mul(Self::conviction_multiplier(periods).into()),
+			);
+			Self::ensure_within_vote_cap(votes.saturating_add(weight))?;
+			Self::count_proposal_voter(proposal_index, &who)?;
+			match vote {
+				Vote::Aye => AyeVotes::<T>::set(proposal_index, &who, votes.saturating_add(weight)),
+				_ => NayVotes::<T>::set(proposal_index, &who, votes.saturating_add(weight)),
+			}
+			Self::add_to_tally(proposal_index, &vote, weight);
+			VoteCounts::<T>::mutate(proposal_index, &who, |count| *count = count.saturating_add(1));
+			Self::record_voted_proposal(&who, proposal_index)?;
+			ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+
+			if periods > 0 {
+				// Extend the lock if the voter already has one
+				let lock_duration = T::LockPeriodLength::get().saturating_mul(periods.into());
+				let (locked, until) = match ConvictionLocks::<T>::get(&who) {
+					Some((locked, until)) =>
+						(locked.max(amount), until.max(current_block.saturating_add(lock_duration))),
+					None => (amount, current_block.saturating_add(lock_duration)),
+				};
+				T::Currency::set_lock(VOTING_ID, &who, locked, WithdrawReasons::all());
+				ConvictionLocks::<T>::insert(&who, (locked, until));
+				Self::deposit_event(Event::TokensLocked { who, amount: locked, until });
+			}
+
+			// Don't expose the voter to the public (to guarantee privacy)
+			Self::deposit_event(Event::ProposalVoted { proposal_index, vote });
+			Ok(Some(T::WeightInfo::vote_with_conviction()).into())
 		}
-		pub fn get_proposal_status(proposal_index: ProposalIndex) -> ProposalStatus {
-			Proposals::<T>::get(proposal_index)
-				.map(|proposal| proposal.status)
-				.expect("Proposal already checked to be registered")
+
+		/*
+			* Unlock tokens
+			* @return DispatchResult
+
+			* This function will release the tokens locked by conviction votes.
+			* The only requirement is that the lock must have expired
+		*/
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::unlock_tokens())]
+		pub fn unlock_tokens(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (amount, until) =
+				ConvictionLocks::<T>::get(&who).ok_or(Error::<T>::NoConvictionLock)?;
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			ensure!(until <= current_block, Error::<T>::LockNotExpired);
+
+			T::Currency::remove_lock(VOTING_ID, &who);
+			ConvictionLocks::<T>::remove(&who);
+
+			Self::deposit_event(Event::TokensUnlocked { who, amount });
+			Ok(())
 		}
-		pub fn get_proposal_end_block(proposal_index: ProposalIndex) -> T::BlockNumber {
-			Proposals::<T>::get(proposal_index)
-				.map(|proposal| proposal.end_block)
-				.expect("Proposal already checked to be registered")
+
+		/*
+			* Emit the current winner
+			* @return DispatchResult
+
+			* This function will deposit an event with the proposal currently leading and its score.
+			* It doesn't change the state of any proposal, so anyone can call it at any time
+			* If there are no votes yet, the proposal index and the score will be 0
+		*/
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::emit_winner(T::MaxProposals::get()))]
+		pub fn emit_winner(origin: OriginFor<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let (proposal_index, score) = Self::get_leader().unwrap_or((0u32.into(), 0));
+			Self::deposit_event(Event::CurrentWinner { proposal_index, score });
+			Ok(())
 		}
-		pub fn voter_has_voted(proposal_index: ProposalIndex, who: &T::AccountId) -> bool {
-			AyeVotes::<T>::contains_key(proposal_index, who)
+
+		/*
+			* Archive a draft
+			 * @param proposal_index: Proposal index to archive
+			* @return DispatchResult
+
+			* This function will move a proposal that has not been started yet out of the proposals storage.
+			* The requirements are:
+				- The proposal must exist and not be started
+				- Only the proposer can archive the proposal
+			* The proposal keeps its index and can be brought back with restore_draft
+		*/
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::archive_draft())]
+		pub fn archive_draft(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.proposer == who, Error::<T>::NotProposer);
+			ensure!(
+				proposal.status == ProposalStatus::NotStarted,
+				Error::<T>::ProposalAlreadyStarted
+			);
+
+			Proposals::<T>::remove(proposal_index);
+			Drafts::<T>::insert(proposal_index, proposal);
+
+			Self::deposit_event(Event::ProposalArchived { proposal_index });
+			Ok(())
 		}
-		pub fn get_aye_votes_balance(
-			proposal_index: ProposalIndex,
-			who: &T::AccountId,
-		) -> BalanceOf<T> {
-			AyeVotes::<T>::get(proposal_index, who)
+
+		/*
+			* Restore a draft
+			 * @param proposal_index: Proposal index to restore
+			* @return DispatchResult
+
+			* This function will move an archived draft back to the proposals storage.
+			* The caller must be the proposer of the draft, and fewer than MaxProposals proposals must be stored
+		*/
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::restore_draft())]
+		pub fn restore_draft(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let proposal: Proposal<T> =
+				Drafts::<T>::get(proposal_index).ok_or(Error::<T>::DraftNotFound)?;
+			ensure!(proposal.proposer == who, Error::<T>::NotProposer);
+			ensure!(Proposals::<T>::count() < T::MaxProposals::get(), Error::<T>::TooManyProposals);
+
+			Drafts::<T>::remove(proposal_index);
+			Proposals::<T>::insert(proposal_index, proposal);
+
+			Self::deposit_event(Event::ProposalRestored { proposal_index });
+			Ok(())
 		}
-		pub fn get_voter_balance(who: &T::AccountId) -> BalanceOf<T> {
-			T::Currency::total_balance(who) - T::Currency::reserved_balance(who)
+
+		/*
+			* Fund the reward pool
+			 * @param amount: Amount of tokens to transfer to the pool
+			* @return DispatchResult
+
+			* This function will transfer tokens from the caller to the reward pool account.
+			* The pool pays PoolReward to the proposer of the winning proposal once it completes
+			* Any signed account can fund the pool
+		*/
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::fund_reward_pool())]
+		pub fn fund_reward_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(amount > 0u32.into(), Error::<T>::InvalidTokensAmountToReserve);
+			T::Currency::transfer(
+				&who,
+				&Self::reward_pool_account(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::RewardPoolFunded { who, amount });
+			Ok(())
 		}
-		pub fn update_proposal_status_to_completed(proposal_index: ProposalIndex) {
-			let proposal: Proposal<T> = Self::get_proposal(proposal_index)
-				.expect("Proposal already checked to be registered");
 
-			let proposal_updated: Proposal<T> = Proposal {
-				proposal_index,
-				text: proposal.text,
-				proposer: proposal.proposer,
-				end_block: proposal.end_block,
-				status: ProposalStatus::Completed,
-			};
-			Proposals::<T>::mutate_exists(&proposal_index, |p| {
-				*p = if let Some(_) = p { Some(proposal_updated) } else { None }
+		/*
+			* Cancel a proposal
+			 * @param proposal_index: Proposal index to cancel
+			* @return DispatchResult
+
+			* This function will remove a proposal that has not been started yet.
+			* The requirements are:
+				- The proposal must exist and not be started
+				- Only the proposer can cancel the proposal
+			* Unlike archive_draft, the proposal is removed for good
+		*/
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::cancel_proposal())]
+		pub fn cancel_proposal(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.proposer == who, Error::<T>::NotProposalOwner);
+			ensure!(
+				proposal.status == ProposalStatus::NotStarted,
+				Error::<T>::ProposalAlreadyStarted
+			);
+
+			Self::settle_deposit(&proposal, true);
+			Proposals::<T>::remove(proposal_index);
+			ProposalTexts::<T>::remove(proposal_index);
+			CustomVotingPeriods::<T>::remove(proposal_index);
+			ProposalsByProposer::<T>::mutate(&who, |count| *count = count.saturating_sub(1));
+			ProposerIndex::<T>::mutate(&who, |indexes| {
+				indexes.retain(|index| *index != proposal_index)
 			});
-		}
+
+			Self::deposit_event(Event::ProposalCancelled { proposal_index });
+			Ok(())
+		}
+
+		/*
+			* Unregister a voter
+			 * @param voter_id: Voter to unregister
+			* @return DispatchResult
+
+			* This function will remove a registered voter and unreserve its reserved tokens, without any penalty.
+			* The votes cast by the voter are removed too, so they no longer count toward the winner
+			* Tokens reserved on behalf of other voters are given back too, the beneficiaries can't vote with them anymore
+			* Proposal deposits stay reserved until their proposals are settled
+			* Conviction locks on the reserved tokens are released
+			* To unregister a voter, the "root user" must call this function passing the user id
+		*/
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::unregister_voter(T::MaxProposals::get()))]
+		pub fn unregister_voter(origin: OriginFor<T>, voter_id: AccountIdOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(Self::is_voter_registered(&voter_id), Error::<T>::NotRegisteredVoter);
+
+			Self::remove_votes(&voter_id);
+			// The beneficiaries can't vote anymore with the tokens of an unregistered sponsor
+			for (beneficiary, sponsored) in Sponsorships::<T>::drain_prefix(&voter_id) {
+				SponsoredIn::<T>::mutate(&beneficiary, |sponsored_in| {
+					*sponsored_in = sponsored_in.saturating_sub(sponsored)
+				});
+			}
+			SponsoredOut::<T>::remove(&voter_id);
+			let reserved_tokens = T::Currency::reserved_balance(&voter_id)
+				.saturating_sub(HeldDeposits::<T>::get(&voter_id));
+			// Tokens given back this way are not slashed, unlike the ones of unreserve_tokens
+			let amount =
+				reserved_tokens.saturating_sub(T::Currency::unreserve(&voter_id, reserved_tokens));
+			if amount > 0u32.into() {
+				Self::deposit_event(Event::TokensRefunded { who: voter_id.clone(), amount });
+			}
+			ReservedAt::<T>::remove(&voter_id);
+			ReserveLocks::<T>::remove(&voter_id);
+			if let Some(delegate) = Delegations::<T>::take(&voter_id) {
+				Delegators::<T>::mutate(&delegate, |delegators| {
+					delegators.retain(|delegator| delegator != &voter_id)
+				});
+			}
+			// Voters delegating to the unregistered voter get their voting power back
+			for delegator in Delegators::<T>::take(&voter_id) {
+				Delegations::<T>::remove(&delegator);
+				Self::deposit_event(Event::Undelegated { who: delegator });
+			}
+			RegisteredVoters::<T>::remove(&voter_id);
+			RegisteredAt::<T>::remove(&voter_id);
+
+			Self::deposit_event(Event::VoterUnregistered { voter_id });
+			Ok(())
+		}
+
+		/*
+			* Change a vote
+			 * @param proposal_index: Proposal index
+			* @param new_vote: Vote replacing the previous one
+			* @param tokens_to_use: Amount of reserved tokens to spend on the new vote
+			* @return DispatchResultWithPostInfo
+
+			* This function will replace the Aye or Nay vote of a voter on a proposal.
+			* The weight of the previous vote is taken out of the tally and its tokens are released before the new vote is applied
+			* The requirements are:
+				- The same ones of vote_proposal, so changing a vote can't get around them
+				- The user must have voted Aye or Nay on the proposal
+				- The user must have enough reserved tokens left to use, counting the released ones
+		*/
+		#[pallet::call_index(18)]
+		#[pallet::weight(
+			T::WeightInfo::change_vote(T::MaxDelegators::get()).max(Pallet::<T>::late_vote_weight())
+		)]
+		pub fn change_vote(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			new_vote: Vote,
+			tokens_to_use: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if !Self::ensure_can_cast_vote(&who, proposal_index)? {
+				Self::deposit_voting_ended(Self::get_winner());
+				return Ok(Self::late_vote_post_info(proposal_index))
+			}
+
+			// Take the previous vote out of the tally, its weight already includes the modifiers
+			let (old_vote, old_weight) = if AyeVotes::<T>::contains_key(proposal_index, &who) {
+				(Vote::Aye, AyeVotes::<T>::take(proposal_index, &who))
+			} else if NayVotes::<T>::contains_key(proposal_index, &who) {
+				(Vote::Nay, NayVotes::<T>::take(proposal_index, &who))
+			} else {
+				return Err(Error::<T>::NotVoted.into())
+			};
+			ProposalTally::<T>::mutate(proposal_index, |(total_ayes, total_nays)| match old_vote {
+				Vote::Aye => *total_ayes = total_ayes.saturating_sub(old_weight),
+				_ => *total_nays = total_nays.saturating_sub(old_weight),
+			});
+			// And release the tokens it used
+			let old_tokens = CommittedTokens::<T>::take(&who, proposal_index);
+			let committed_reserve =
+				ProposalCommittedReserve::<T>::get(proposal_index).saturating_sub(old_tokens);
+
+			match new_vote {
+				Vote::Aye | Vote::Nay => {
+					ensure!(
+						tokens_to_use > 0u32.into() &&
+							Self::get_available_reserve(&who) >= tokens_to_use,
+						Error::<T>::NotEnoughReservedTokens
+					);
+					let committed_reserve = committed_reserve.saturating_add(tokens_to_use);
+					ensure!(
+						committed_reserve <= T::MaxProposalReserve::get(),
+						Error::<T>::ProposalReserveCapReached
+					);
+					let weight = Self::vote_weight(&who, proposal_index, tokens_to_use);
+					Self::ensure_within_vote_cap(weight)?;
+					match new_vote {
+						Vote::Aye => AyeVotes::<T>::insert(proposal_index, &who, weight),
+						_ => NayVotes::<T>::insert(proposal_index, &who, weight),
+					}
+					Self::add_to_tally(proposal_index, &new_vote, weight);
+					CommittedTokens::<T>::insert(&who, proposal_index, tokens_to_use);
+					ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+				},
+				Vote::Abstain => {
+					AbstainVotes::<T>::insert(proposal_index, &who, ());
+					ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+				},
+			};
+
+			// Don't expose the voter to the public (to guarantee privacy)
+			Self::deposit_event(Event::VoteChanged { proposal_index, vote: new_vote });
+			Ok(Some(T::WeightInfo::change_vote(Self::delegator_count(&who))).into())
+		}
+
+		/*
+			* Delegate voting power
+			 * @param to: Voter receiving the voting power
+			* @return DispatchResult
+
+			* This function will let another voter vote with the caller's available reserve.
+			* While delegating, the caller can't vote directly nor unreserve tokens
+			* The requirements are:
+				- Both voters must be registered
+				- The delegation can't lead back to the caller, directly or through other delegations
+				- No tokens of the caller, nor of a previous delegation, can be committed to proposals
+				- At most MaxDelegators voters can end up delegating to the same voter
+		*/
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::delegate(T::MaxDelegators::get()))]
+		pub fn delegate(origin: OriginFor<T>, to: AccountIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
+			ensure!(Self::is_voter_registered(&to), Error::<T>::NotRegisteredVoter);
+			ensure!(!Self::delegation_leads_to(&to, &who), Error::<T>::DelegationCycle);
+			Self::ensure_delegated_tokens_uncommitted(&who)?;
+			// The whole tree behind the caller moves under the delegate, unless it already is there
+			let root = Self::delegation_root(&to);
+			if Self::delegation_root(&who) != root {
+				let moved = Self::delegators_of(&who).len().saturating_add(1);
+				ensure!(
+					Self::delegators_of(&root).len().saturating_add(moved) <=
+						T::MaxDelegators::get() as usize,
+					Error::<T>::TooManyDelegators
+				);
+			}
+
+			if let Some(previous) = Delegations::<T>::get(&who) {
+				Delegators::<T>::mutate(&previous, |delegators| {
+					delegators.retain(|delegator| delegator != &who)
+				});
+			}
+			Delegators::<T>::try_mutate(&to, |delegators| delegators.try_push(who.clone()))
+				.map_err(|_| Error::<T>::TooManyDelegators)?;
+			Delegations::<T>::insert(&who, &to);
+			Self::deposit_event(Event::Delegated { who, to });
+			Ok(())
+		}
+
+		/*
+			* Undelegate voting power
+			* @return DispatchResult
+
+			* This function will take back the voting power delegated by the caller.
+			* The requirements are:
+				- The user must be a registered voter
+				- The user must have delegated its voting power
+				- None of the delegated tokens can be committed to proposals
+		*/
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::undelegate(T::MaxDelegators::get()))]
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
+			let delegate = Delegations::<T>::get(&who).ok_or(Error::<T>::NotDelegated)?;
+			Self::ensure_delegated_tokens_uncommitted(&who)?;
+
+			Delegators::<T>::mutate(&delegate, |delegators| {
+				delegators.retain(|delegator| delegator != &who)
+			});
+			Delegations::<T>::remove(&who);
+			Self::deposit_event(Event::Undelegated { who });
+			Ok(())
+		}
+
+		/*
+			* Commit a hidden vote
+			 * @param proposal_index: Proposal index
+			* @param commitment: Hash of (vote, tokens, salt)
+			* @return DispatchResult
+
+			* This function will store a vote whose direction and tokens stay hidden until it is revealed.
+			* The vote only counts once it is revealed with reveal_vote during the RevealPeriod that follows the proposal end
+			* The requirements are:
+				- The user must be a registered voter
+				- The proposal must be registered and active
+				- The voting period must be still live
+				- The user must not have voted or committed a vote on the proposal
+		*/
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::commit_vote())]
+		pub fn commit_vote(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			commitment: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if !Self::ensure_can_vote(&who, proposal_index)? {
+				Self::deposit_voting_ended(Self::get_winner());
+				return Ok(())
+			}
+			ensure!(Self::has_proposed_enough(&who), Error::<T>::MustProposeFirst);
+			ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::VotingPowerDelegated);
+			Self::ensure_not_own_proposal(&who, proposal_index)?;
+
+			ensure!(
+				!Self::voter_has_voted(proposal_index, &who) &&
+					!VoteCommitments::<T>::contains_key(proposal_index, &who),
+				Error::<T>::VoterAlreadyVoted
+			);
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			VoteCommitments::<T>::insert(proposal_index, &who, (commitment, current_block));
+
+			Self::deposit_event(Event::VoteCommitted { proposal_index });
+			Ok(())
+		}
+
+		/*
+			* Reveal a committed vote
+			 * @param proposal_index: Proposal index
+			* @param vote: Committed vote
+			* @param tokens: Committed amount of reserved tokens
+			* @param salt: Salt used in the commitment
+			* @return DispatchResult
+
+			* This function will check the vote against its commitment and add it to the tally.
+			* The weight decays as if the vote was cast when it was committed
+			* The requirements are:
+				- The user must be a registered voter and not be delegating its voting power
+				- The user must not have voted directly on the proposal
+				- The proposal must have ended and still be active
+				- The RevealPeriod after the proposal end must not be over
+				- The hash of (vote, tokens, salt) must match the commitment
+				- The user must have enough reserved tokens left to use
+		*/
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::reveal_vote())]
+		pub fn reveal_vote(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			vote: Vote,
+			tokens: BalanceOf<T>,
+			salt: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
+			ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::VotingPowerDelegated);
+			ensure!(Self::is_proposal_active(proposal_index), Error::<T>::ProposalNotActive);
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			let end_block = Self::get_proposal_end_block(proposal_index)?;
+			ensure!(end_block <= current_block, Error::<T>::RevealTooEarly);
+			ensure!(
+				current_block < Self::completion_block(end_block),
+				Error::<T>::RevealPeriodEnded
+			);
+			let (commitment, committed_at) = VoteCommitments::<T>::get(proposal_index, &who)
+				.ok_or(Error::<T>::CommitmentNotFound)?;
+			// The commitment is the only vote the voter can have on the proposal
+			ensure!(!Self::voter_has_voted(proposal_index, &who), Error::<T>::VoterAlreadyVoted);
+			ensure!(
+				T::Hashing::hash_of(&(&vote, tokens, salt)) == commitment,
+				Error::<T>::InvalidReveal
+			);
+
+			Self::count_proposal_voter(proposal_index, &who)?;
+			match vote {
+				Vote::Aye | Vote::Nay => {
+					ensure!(
+						tokens > 0u32.into() && Self::get_available_reserve(&who) >= tokens,
+						Error::<T>::NotEnoughReservedTokens
+					);
+					let committed_reserve =
+						ProposalCommittedReserve::<T>::get(proposal_index).saturating_add(tokens);
+					ensure!(
+						committed_reserve <= T::MaxProposalReserve::get(),
+						Error::<T>::ProposalReserveCapReached
+					);
+					let weight = Self::vote_weight_at(&who, proposal_index, tokens, committed_at);
+					Self::ensure_within_vote_cap(weight)?;
+					match vote {
+						Vote::Aye => AyeVotes::<T>::insert(proposal_index, &who, weight),
+						_ => NayVotes::<T>::insert(proposal_index, &who, weight),
+					}
+					Self::add_to_tally(proposal_index, &vote, weight);
+					VoteCounts::<T>::insert(proposal_index, &who, 1);
+					Self::record_voted_proposal(&who, proposal_index)?;
+					CommittedTokens::<T>::insert(&who, proposal_index, tokens);
+					ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+				},
+				Vote::Abstain => AbstainVotes::<T>::insert(proposal_index, &who, ()),
+			};
+			VoteCommitments::<T>::remove(proposal_index, &who);
+
+			Self::deposit_event(Event::VoteRevealed { proposal_index, vote });
+			Ok(())
+		}
+
+		/*
+			* Claim a refund
+			 * @param proposal_index: Completed, vetoed or rejected proposal index
+			* @return DispatchResult
+
+			* This function will unreserve, without any penalty, the tokens the caller spent on a proposal.
+			* The tokens can only be claimed once
+			* The requirements are:
+				- The proposal must be completed, vetoed or rejected
+				- The user must have tokens committed to the proposal
+				- The user's reserved tokens can't be locked by a conviction vote
+		*/
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::claim_refund())]
+		pub fn claim_refund(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				matches!(
+					Self::get_proposal_status(proposal_index)?,
+					ProposalStatus::Completed | ProposalStatus::Rejected | ProposalStatus::Vetoed
+				),
+				Error::<T>::ProposalNotCompleted
+			);
+			ensure!(Self::locked_reserve(&who) == 0u32.into(), Error::<T>::TokensLocked);
+			let committed = CommittedTokens::<T>::take(&who, proposal_index);
+			ensure!(committed > 0u32.into(), Error::<T>::NotReservedTokens);
+
+			Self::release_committed_tokens(proposal_index, who, committed);
+			Ok(())
+		}
+
+		/*
+			* Reject a proposal
+			 * @param proposal_index: Proposal index to reject
+			* @return DispatchResult
+
+			* This function will close a proposal for good, for instance when its text is spam or offensive.
+			* A rejected proposal can't be voted on nor win, and its voters can claim back their committed tokens
+			* The requirements are:
+				- The proposal must exist and be neither completed, vetoed nor rejected
+			* To reject a proposal, the "root user" must call this function passing the proposal index
+		*/
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::reject_proposal())]
+		pub fn reject_proposal(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(
+				matches!(proposal.status, ProposalStatus::NotStarted | ProposalStatus::InProgress),
+				Error::<T>::ProposalAlreadyClosed
+			);
+
+			Self::settle_deposit(&proposal, false);
+			Self::settle_start_fee(proposal_index, false);
+			let proposal_updated =
+				Proposal { status: ProposalStatus::Rejected, deposit: 0u32.into(), ..proposal };
+			Proposals::<T>::insert(proposal_index, proposal_updated);
+
+			Self::deposit_event(Event::ProposalRejected { proposal_index });
+			Ok(())
+		}
+
+		/*
+			* Vote a proposal with voice credits
+			 * @param proposal_index: Proposal index
+			* @param vote: Vote
+			* @param credits: Number of votes to cast
+			* @return DispatchResult
+
+			* This function will reserve exactly credits * credits tokens and spend them on the vote.
+			* This makes the quadratic cost explicit, with no reserved tokens lost to rounding
+			* The requirements are:
+				- The same ones of reserve_tokens and vote_proposal
+				- The user must have enough free balance to pay credits * credits tokens
+		*/
+		#[pallet::call_index(25)]
+		#[pallet::weight(
+			T::WeightInfo::vote_with_credits().max(Pallet::<T>::late_vote_weight())
+		)]
+		pub fn vote_with_credits(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			vote: Vote,
+			credits: BalanceOf<T>,
+		) -> DispatchResult {
+			let cost = credits.saturating_mul(credits);
+			Self::reserve_tokens(origin.clone(), cost)?;
+			Self::vote_proposal(origin, proposal_index, cost, vote, 0)
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		/*
+			* Register a batch of voters
+			 * @param voters: Voters to register, together with their registration fee
+			* @return DispatchResult
+
+			* This function will register every voter of the batch the same way register_voter does.
+			* Already registered voters are skipped, so their balance is never re-initialized
+			* The requirements are:
+				- The batch must hold at most MaxBatchSize voters
+				- Every fee must be valid, otherwise no voter is registered
+			* To register a batch of voters, the "root user" must call this function passing the voters and their fees
+		*/
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::register_voters(voters.len() as u32))]
+		pub fn register_voters(
+			origin: OriginFor<T>,
+			voters: Vec<(AccountIdOf<T>, BalanceOf<T>)>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(voters.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::TooManyVoters);
+
+			let mut count = 0u32;
+			for (voter_id, fee) in voters {
+				if Self::is_voter_registered(&voter_id) {
+					continue
+				}
+				Self::do_register_voter(voter_id, fee, false)?;
+				count += 1;
+			}
+
+			Self::deposit_event(Event::VotersRegistered { count });
+			Ok(())
+		}
+
+		/*
+			* Close a proposal
+			 * @param proposal_index: Proposal index to close
+			* @return DispatchResult
+
+			* This function will complete a proposal whose voting period is over, without having to cast a vote.
+			* The winner among all the proposals is computed and announced in the VotingEnded event
+			* The requirements are:
+				- The user must be a registered voter
+				- The proposal must be registered and active
+				- The voting period and the reveal period after it must be over
+		*/
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::close_proposal(T::MaxProposals::get()))]
+		pub fn close_proposal(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_voter_registered(&who), Error::<T>::NotRegisteredVoter);
+			ensure!(Self::is_proposal_registered(proposal_index), Error::<T>::ProposalNotFound);
+			ensure!(Self::is_proposal_active(proposal_index), Error::<T>::ProposalNotActive);
+
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			let proposal_end_block = Self::get_proposal_end_block(proposal_index)?;
+			ensure!(
+				Self::completion_block(proposal_end_block) <= current_block,
+				Error::<T>::VotingStillActive
+			);
+
+			Self::update_proposal_status_to_completed(proposal_index)?;
+			Self::deposit_voting_ended(Self::get_winner());
+			Ok(())
+		}
+
+		/*
+			* Add votes to a proposal
+			 * @param proposal_index: Proposal index
+			* @param vote: Side of the existing vote to top up
+			* @param extra_tokens: Amount of reserved tokens to add to the vote
+			* @return DispatchResultWithPostInfo
+
+			* This function will add the quadratic weight of the extra tokens to the Aye or Nay vote of a voter on a proposal.
+			* The extra tokens are committed to the proposal the same way the ones of the original vote are
+			* The requirements are:
+				- The user must be a registered voter
+				- The proposal must be registered and active
+				- The voting period must be still live
+				- The user must have voted Aye or Nay on the proposal, on the same side as the given vote
+				- The user must not have committed a hidden vote on the proposal
+				- The user must have enough reserved tokens left to use
+		*/
+		#[pallet::call_index(28)]
+		#[pallet::weight(
+			T::WeightInfo::add_votes(T::MaxDelegators::get()).max(Pallet::<T>::late_vote_weight())
+		)]
+		pub fn add_votes(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			vote: Vote,
+			extra_tokens: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if !Self::ensure_can_vote(&who, proposal_index)? {
+				Self::deposit_voting_ended(Self::get_winner());
+				return Ok(Self::late_vote_post_info(proposal_index))
+			}
+			ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::VotingPowerDelegated);
+			Self::ensure_no_pending_commitment(&who, proposal_index)?;
+
+			// The top up must keep the side of the original vote
+			let voted = if AyeVotes::<T>::contains_key(proposal_index, &who) {
+				Vote::Aye
+			} else if NayVotes::<T>::contains_key(proposal_index, &who) {
+				Vote::Nay
+			} else {
+				return Err(Error::<T>::NotVoted.into())
+			};
+			ensure!(voted == vote, Error::<T>::VoteDirectionMismatch);
+
+			ensure!(
+				extra_tokens > 0u32.into() && Self::get_available_reserve(&who) >= extra_tokens,
+				Error::<T>::NotEnoughReservedTokens
+			);
+			let committed_reserve =
+				ProposalCommittedReserve::<T>::get(proposal_index).saturating_add(extra_tokens);
+			ensure!(
+				committed_reserve <= T::MaxProposalReserve::get(),
+				Error::<T>::ProposalReserveCapReached
+			);
+
+			let weight = Self::vote_weight(&who, proposal_index, extra_tokens);
+			let votes = match vote {
+				Vote::Aye => Self::get_aye_votes_balance(proposal_index, &who),
+				_ => Self::get_nay_votes_balance(proposal_index, &who),
+			}
+			.saturating_add(weight);
+			Self::ensure_within_vote_cap(votes)?;
+			match vote {
+				Vote::Aye => AyeVotes::<T>::insert(proposal_index, &who, votes),
+				_ => NayVotes::<T>::insert(proposal_index, &who, votes),
+			}
+			Self::add_to_tally(proposal_index, &vote, weight);
+			CommittedTokens::<T>::mutate(&who, proposal_index, |committed| {
+				*committed = committed.saturating_add(extra_tokens)
+			});
+			ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+
+			// Don't expose the voter to the public (to guarantee privacy)
+			Self::deposit_event(Event::ProposalVoted { proposal_index, vote });
+			Ok(Some(T::WeightInfo::add_votes(Self::delegator_count(&who))).into())
+		}
+
+		/*
+			* Set the options of a proposal
+			 * @param proposal_index: Proposal index
+			* @param options: Hashes of the options offered by the proposal
+			* @return DispatchResult
+
+			* This function will turn a proposal into a multi-choice proposal, voted with vote_option.
+			* Setting the options again replaces the previous ones
+			* The requirements are:
+				- The proposal must exist and not be started
+				- Only the proposer can set the options
+				- There must be at most MaxOptions options
+		*/
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::set_proposal_options(options.len() as u32))]
+		pub fn set_proposal_options(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			options: Vec<T::Hash>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.proposer == who, Error::<T>::NotProposer);
+			ensure!(
+				proposal.status == ProposalStatus::NotStarted,
+				Error::<T>::ProposalAlreadyStarted
+			);
+			let options: BoundedVec<T::Hash, T::MaxOptions> =
+				options.try_into().map_err(|_| Error::<T>::TooManyOptions)?;
+			let count = options.len() as u32;
+
+			Proposals::<T>::insert(proposal_index, Proposal { options, ..proposal });
+
+			Self::deposit_event(Event::ProposalOptionsSet { proposal_index, count });
+			Ok(())
+		}
+
+		/*
+			* Vote an option of a proposal
+			 * @param proposal_index: Proposal index
+			* @param option_index: Index of the option in the options of the proposal
+			* @param tokens: Amount of reserved tokens to spend on the option
+			* @return DispatchResultWithPostInfo
+
+			* This function will add the quadratic weight of the tokens to the votes of an option.
+			* Tokens can be spread across several options, each of them is weighted on its own
+			* The winner within the proposal is the option with the most votes
+			* The requirements are:
+				- Voting must not be paused
+				- The user must be a registered voter
+				- The proposal must be registered and active
+				- The voting period must be still live
+				- The option must be one of the options of the proposal
+				- The user must not have committed a hidden vote on the proposal
+				- The user must have enough reserved tokens left to use
+				- The proposal must have room for another voter
+		*/
+		#[pallet::call_index(30)]
+		#[pallet::weight(
+			T::WeightInfo::vote_option(T::MaxDelegators::get()).max(Pallet::<T>::late_vote_weight())
+		)]
+		pub fn vote_option(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			option_index: OptionIndex,
+			tokens: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if !Self::ensure_can_cast_vote(&who, proposal_index)? {
+				Self::deposit_voting_ended(Self::get_winner());
+				return Ok(Self::late_vote_post_info(proposal_index))
+			}
+
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!((option_index as usize) < proposal.options.len(), Error::<T>::InvalidOption);
+			ensure!(
+				tokens > 0u32.into() && Self::get_available_reserve(&who) >= tokens,
+				Error::<T>::NotEnoughReservedTokens
+			);
+			let committed_reserve =
+				ProposalCommittedReserve::<T>::get(proposal_index).saturating_add(tokens);
+			ensure!(
+				committed_reserve <= T::MaxProposalReserve::get(),
+				Error::<T>::ProposalReserveCapReached
+			);
+
+			// Quadratic voting logic, applied to each option on its own
+			let weight = Self::repeat_vote_weight(
+				proposal_index,
+				&who,
+				Self::vote_weight(&who, proposal_index, tokens),
+			);
+			let votes =
+				OptionVotes::<T>::get((proposal_index, option_index), &who).saturating_add(weight);
+			Self::ensure_within_vote_cap(votes)?;
+			Self::count_proposal_voter(proposal_index, &who)?;
+			OptionVotes::<T>::insert((proposal_index, option_index), &who, votes);
+			OptionTally::<T>::mutate((proposal_index, option_index), |total| {
+				*total = total.saturating_add(weight)
+			});
+			VoteCounts::<T>::mutate(proposal_index, &who, |count| *count = count.saturating_add(1));
+			Self::record_voted_proposal(&who, proposal_index)?;
+			CommittedTokens::<T>::mutate(&who, proposal_index, |committed| {
+				*committed = committed.saturating_add(tokens)
+			});
+			ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+
+			// Don't expose the voter to the public (to guarantee privacy)
+			Self::deposit_event(Event::OptionVoted { proposal_index, option_index });
+			Ok(Some(T::WeightInfo::vote_option(Self::delegator_count(&who))).into())
+		}
+
+		/*
+			* Force start a proposal
+			 * @param proposal_index: Proposal index to start
+			* @return DispatchResult
+
+			* This function will start a proposal the same way start_proposal does, without charging any fee.
+			* The voting period of the proposal starts counting from this block
+			* The requirements are:
+				- The proposal must exist and not be started
+			* To force start a proposal, the "root user" must call this function passing the proposal index
+		*/
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::force_start_proposal())]
+		pub fn force_start_proposal(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(
+				proposal.status == ProposalStatus::NotStarted,
+				Error::<T>::ProposalAlreadyStarted
+			);
+
+			// The voting period chosen at creation only starts running now
+			let start_block = <frame_system::Pallet<T>>::block_number();
+			let end_block = start_block + proposal.end_block.saturating_sub(proposal.created_at);
+			let proposal_updated: Proposal<T> = Proposal {
+				end_block,
+				start_block,
+				start_fee: 0u32.into(),
+				completed_at: None,
+				status: ProposalStatus::InProgress,
+				..proposal
+			};
+
+			Proposals::<T>::insert(proposal_index, proposal_updated);
+			Self::schedule_expiry(proposal_index, end_block);
+
+			Self::deposit_event(Event::ProposalStarted { proposal_index, end_block });
+			Ok(())
+		}
+
+		/*
+			* Pause the pallet
+			 * @return DispatchResult
+
+			* This function will halt proposal creation, proposal starts and voting until resume is called.
+			* Reads, unreserves and refunds keep working while paused
+			* To pause the pallet, the "root user" must call this function
+		*/
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			Paused::<T>::put(true);
+			Self::deposit_event(Event::PalletPaused);
+			Ok(())
+		}
+
+		/*
+			* Resume the pallet
+			 * @return DispatchResult
+
+			* This function will let voting activity continue after a pause.
+			* To resume the pallet, the "root user" must call this function
+		*/
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::resume())]
+		pub fn resume(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			Paused::<T>::kill();
+			Self::deposit_event(Event::PalletResumed);
+			Ok(())
+		}
+
+		/*
+			* Extend a proposal
+			 * @param proposal_index: Proposal index to extend
+			 * @param extra_blocks: Blocks added to the end block of the proposal
+			* @return DispatchResult
+
+			* This function will give an active proposal more time to be voted, moving its end block.
+			* The requirements are:
+				- The proposal must exist and be in progress
+			* To extend a proposal, the "root user" must call this function passing the proposal index and the extra blocks
+		*/
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::extend_proposal())]
+		pub fn extend_proposal(
+			origin: OriginFor<T>,
+			proposal_index: ProposalIndexOf<T>,
+			extra_blocks: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.status == ProposalStatus::InProgress, Error::<T>::ProposalNotActive);
+
+			let new_end_block = proposal.end_block.saturating_add(extra_blocks);
+			Proposals::<T>::insert(
+				proposal_index,
+				Proposal { end_block: new_end_block, ..proposal },
+			);
+			// The entry at the old end block is skipped once it no longer matches
+			Self::schedule_expiry(proposal_index, new_end_block);
+
+			Self::deposit_event(Event::ProposalExtended { proposal_index, new_end_block });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn is_paused() -> bool {
+			Paused::<T>::get()
+		}
+		pub fn is_voter_registered(who: &T::AccountId) -> bool {
+			RegisteredVoters::<T>::contains_key(who)
+		}
+		// Credit the initial balance of a voter not registered yet, unless the balance the
+		// account already holds is preserved (no fee is collected then)
+		pub fn do_register_voter(
+			voter_id: AccountIdOf<T>,
+			fee: BalanceOf<T>,
+			preserve_balance: bool,
+		) -> DispatchResult {
+			let initial_balance = if preserve_balance {
+				Self::get_free_voting_balance(&voter_id)
+			} else {
+				let initial_balance = Self::initial_voter_balance(fee)?;
+				// Added on top of any balance the account already holds
+				let _ = T::Currency::deposit_creating(&voter_id, initial_balance);
+				// The fee is the part of the initial 100 tokens the voter doesn't get, so it is
+				// never minted
+
+				Self::deposit_event(Event::BalanceInitialized {
+					who: voter_id.clone(),
+					amount: initial_balance,
+				});
+				Self::deposit_event(Event::RegistrationFeeCollected {
+					who: voter_id.clone(),
+					amount: fee,
+				});
+				initial_balance
+			};
+
+			RegisteredVoters::<T>::insert(&voter_id, true);
+			RegisteredAt::<T>::insert(&voter_id, <frame_system::Pallet<T>>::block_number());
+			Self::deposit_event(Event::VoterRegistered { voter_id, initial_balance });
+			Ok(())
+		}
+		// Create initial balance for the voter equals to 100 - fee
+		pub fn initial_voter_balance(fee: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+			ensure!(fee > 0u32.into(), Error::<T>::InsufficientFee);
+			let initial_balance_without_fee: BalanceOf<T> = 100u32.into();
+			ensure!(
+				Self::checked_sub_between_balances(initial_balance_without_fee, fee).is_ok(),
+				Error::<T>::SubstractionOverflow
+			);
+			Self::checked_sub_between_balances(initial_balance_without_fee, fee)
+		}
+		pub fn has_proposed_enough(who: &T::AccountId) -> bool {
+			ProposalsByProposer::<T>::get(who) >= T::MinProposalsToVote::get()
+		}
+		pub fn is_proposal_registered(proposal_index: ProposalIndexOf<T>) -> bool {
+			Proposals::<T>::contains_key(proposal_index)
+		}
+		pub fn is_proposal_active(proposal_index: ProposalIndexOf<T>) -> bool {
+			match Proposals::<T>::get(proposal_index) {
+				Some(proposal) =>
+					proposal.status == ProposalStatus::InProgress &&
+						Self::is_proposal_registered(proposal_index),
+				_ => false,
+			}
+		}
+		pub fn get_proposal(proposal_index: ProposalIndexOf<T>) -> Option<Proposal<T>> {
+			Proposals::<T>::get(proposal_index)
+		}
+		pub fn proposal_timeline(
+			proposal_index: ProposalIndexOf<T>,
+		) -> Option<ProposalTimeline<T>> {
+			Proposals::<T>::get(proposal_index).map(|proposal| ProposalTimeline {
+				created_at: proposal.created_at,
+				started_at: match proposal.status {
+					ProposalStatus::NotStarted => None,
+					_ => Some(proposal.start_block),
+				},
+				end_block: proposal.end_block,
+				completed_at: proposal.completed_at,
+			})
+		}
+		pub fn get_proposal_status(
+			proposal_index: ProposalIndexOf<T>,
+		) -> Result<ProposalStatus, DispatchError> {
+			Proposals::<T>::get(proposal_index)
+				.map(|proposal| proposal.status)
+				.ok_or(Error::<T>::ProposalNotFound.into())
+		}
+		pub fn get_proposal_end_block(
+			proposal_index: ProposalIndexOf<T>,
+		) -> Result<T::BlockNumber, DispatchError> {
+			Proposals::<T>::get(proposal_index)
+				.map(|proposal| proposal.end_block)
+				.ok_or(Error::<T>::ProposalNotFound.into())
+		}
+		pub fn get_proposal_created_block(
+			proposal_index: ProposalIndexOf<T>,
+		) -> Result<T::BlockNumber, DispatchError> {
+			Proposals::<T>::get(proposal_index)
+				.map(|proposal| proposal.created_at)
+				.ok_or(Error::<T>::ProposalNotFound.into())
+		}
+		pub fn get_proposal_start_block(
+			proposal_index: ProposalIndexOf<T>,
+		) -> Result<T::BlockNumber, DispatchError> {
+			Proposals::<T>::get(proposal_index)
+				.map(|proposal| proposal.start_block)
+				.ok_or(Error::<T>::ProposalNotFound.into())
+		}
+		// When AllowSelfVoting is disabled, proposers can't vote their own proposals
+		pub fn ensure_not_own_proposal(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			if T::AllowSelfVoting::get() {
+				return Ok(())
+			}
+			let proposal =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.proposer != *who, Error::<T>::CannotVoteOwnProposal);
+			Ok(())
+		}
+		// Single Aye vote of a vote_multiple_proposals batch, the batch checks already passed
+		pub fn vote_in_batch(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+			tokens_to_use: BalanceOf<T>,
+			vote: Vote,
+		) -> DispatchResult {
+			ensure!(vote == Vote::Aye, Error::<T>::VoteNotSupportedInBatch);
+			// Debited from what the previous votes of the batch left
+			ensure!(
+				tokens_to_use > 0u32.into() && Self::get_available_reserve(who) >= tokens_to_use,
+				Error::<T>::NotEnoughReservedTokens
+			);
+			let committed_reserve =
+				ProposalCommittedReserve::<T>::get(proposal_index).saturating_add(tokens_to_use);
+			ensure!(
+				committed_reserve <= T::MaxProposalReserve::get(),
+				Error::<T>::ProposalReserveCapReached
+			);
+			let aye_votes = Self::get_aye_votes_balance(proposal_index, who);
+			// Quadratic voting logic
+			let weight = Self::repeat_vote_weight(
+				proposal_index,
+				who,
+				Self::vote_weight(who, proposal_index, tokens_to_use),
+			);
+			ensure!(
+				Self::checked_add_between_balances(aye_votes, weight).is_ok(),
+				Error::<T>::AdditionOverflow
+			);
+			let new_aye_votes = Self::checked_add_between_balances(aye_votes, weight)
+				.expect("Addition already checked; QEP");
+			Self::ensure_within_vote_cap(new_aye_votes)?;
+			Self::count_proposal_voter(proposal_index, who)?;
+			log::debug!(
+				target: LOG_TARGET,
+				"multiple vote on proposal {:?}: committed {:?}, weight {:?}",
+				proposal_index,
+				tokens_to_use,
+				weight
+			);
+			log::trace!(
+				target: LOG_TARGET,
+				"voter tally on proposal {:?}: {:?} -> {:?}",
+				proposal_index,
+				aye_votes,
+				new_aye_votes
+			);
+
+			AyeVotes::<T>::set(proposal_index, who, new_aye_votes);
+			Self::add_to_tally(proposal_index, &vote, weight);
+			VoteCounts::<T>::mutate(proposal_index, who, |count| *count = count.saturating_add(1));
+			Self::record_voted_proposal(who, proposal_index)?;
+			CommittedTokens::<T>::mutate(who, proposal_index, |committed| {
+				*committed = committed.saturating_add(tokens_to_use)
+			});
+			ProposalCommittedReserve::<T>::insert(proposal_index, committed_reserve);
+			Ok(())
+		}
+		// Checks shared by every vote path, so they can't diverge. Once the voting and reveal
+		// periods are over the proposal is completed instead and false tells the caller not
+		// to count the vote
+		fn ensure_can_vote(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+		) -> Result<bool, DispatchError> {
+			ensure!(Self::is_voter_registered(who), Error::<T>::NotRegisteredVoter);
+			ensure!(Self::is_proposal_registered(proposal_index), Error::<T>::ProposalNotFound);
+			ensure!(Self::is_proposal_active(proposal_index), Error::<T>::ProposalNotActive);
+			if Self::is_voting_period_over(proposal_index)? {
+				let current_block = <frame_system::Pallet<T>>::block_number();
+				let end_block = Self::get_proposal_end_block(proposal_index)?;
+				ensure!(
+					Self::completion_block(end_block) <= current_block,
+					Error::<T>::VotingPeriodEnded
+				);
+				Self::update_proposal_status_to_completed(proposal_index)?;
+				Self::deposit_event(Event::LateVoteIgnored { proposal_index, who: who.clone() });
+				return Ok(false)
+			}
+			Ok(true)
+		}
+		pub fn is_voting_period_over(
+			proposal_index: ProposalIndexOf<T>,
+		) -> Result<bool, DispatchError> {
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			Ok(Self::get_proposal_end_block(proposal_index)? <= current_block)
+		}
+		// The votes of a single account on a proposal can't go over MaxVotesPerVoter
+		pub fn ensure_within_vote_cap(votes: BalanceOf<T>) -> DispatchResult {
+			ensure!(votes <= T::MaxVotesPerVoter::get().into(), Error::<T>::VoteCapExceeded);
+			Ok(())
+		}
+		// When RequirePreReserve is enabled, only reservations made up to the proposal start count
+		pub fn ensure_reserved_before_start(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			if !T::RequirePreReserve::get() {
+				return Ok(())
+			}
+			let reserved_at = ReservedAt::<T>::get(who).ok_or(Error::<T>::ReservedTooLate)?;
+			ensure!(
+				reserved_at <= Self::get_proposal_start_block(proposal_index)?,
+				Error::<T>::ReservedTooLate
+			);
+			Ok(())
+		}
+		// Checks of every new vote on top of ensure_can_vote, shared by the direct vote paths
+		fn ensure_can_cast_vote(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+		) -> Result<bool, DispatchError> {
+			ensure!(!Self::is_paused(), Error::<T>::PalletPaused);
+			if !Self::ensure_can_vote(who, proposal_index)? {
+				return Ok(false)
+			}
+			ensure!(Self::has_proposed_enough(who), Error::<T>::MustProposeFirst);
+			ensure!(!Delegations::<T>::contains_key(who), Error::<T>::VotingPowerDelegated);
+			Self::ensure_not_own_proposal(who, proposal_index)?;
+			Self::ensure_reserved_before_start(who, proposal_index)?;
+			Self::ensure_no_pending_commitment(who, proposal_index)?;
+			Ok(true)
+		}
+		// Worst case of a vote completing its expired proposal instead, the vote calls charge it
+		// up front and refund what the path taken didn't use. The voters are only gone through
+		// when AutoUnreserveOnCompletion refunds them
+		pub fn late_vote_weight() -> Weight {
+			let voters = if T::AutoUnreserveOnCompletion::get() {
+				T::MaxVotersPerProposal::get()
+			} else {
+				0
+			};
+			T::WeightInfo::late_vote(T::MaxProposals::get(), voters)
+		}
+		// Actual weight of a vote that completed the proposal instead
+		pub fn late_vote_post_info(proposal_index: ProposalIndexOf<T>) -> PostDispatchInfo {
+			let voters = if T::AutoUnreserveOnCompletion::get() {
+				Self::voter_count(proposal_index)
+			} else {
+				0
+			};
+			Some(T::WeightInfo::late_vote(Proposals::<T>::count(), voters)).into()
+		}
+		// Voters whose reserve is read through the delegation, the d component of the vote weights
+		pub fn delegator_count(who: &T::AccountId) -> u32 {
+			Self::delegators_of(who).len() as u32
+		}
+		// A committed vote only counts once revealed, so the voter can't also vote directly
+		pub fn ensure_no_pending_commitment(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			ensure!(
+				!VoteCommitments::<T>::contains_key(proposal_index, who),
+				Error::<T>::CommitmentPending
+			);
+			Ok(())
+		}
+		pub fn voter_has_voted(proposal_index: ProposalIndexOf<T>, who: &T::AccountId) -> bool {
+			AyeVotes::<T>::contains_key(proposal_index, who) ||
+				NayVotes::<T>::contains_key(proposal_index, who) ||
+				AbstainVotes::<T>::contains_key(proposal_index, who)
+		}
+		// (registered, has_voted) of every account, truncated to MaxBulkStatusAccounts
+		pub fn bulk_voting_status(
+			accounts: Vec<AccountIdOf<T>>,
+			proposal_index: ProposalIndexOf<T>,
+		) -> Vec<(AccountIdOf<T>, bool, bool)> {
+			accounts
+				.into_iter()
+				.take(T::MaxBulkStatusAccounts::get() as usize)
+				.map(|who| {
+					let registered = Self::is_voter_registered(&who);
+					let has_voted = Self::voter_has_voted(proposal_index, &who);
+					(who, registered, has_voted)
+				})
+				.collect()
+		}
+		pub fn can_vote(proposal_index: ProposalIndexOf<T>, who: &T::AccountId) -> bool {
+			T::AllowRepeatVotes::get() || !Self::voter_has_voted(proposal_index, who)
+		}
+		// Every previous vote on the proposal discounts the weight once more
+		pub fn repeat_vote_weight(
+			proposal_index: ProposalIndexOf<T>,
+			who: &T::AccountId,
+			weight: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let mut weight = weight;
+			for _ in 0..VoteCounts::<T>::get(proposal_index, who) {
+				if weight == 0u32.into() {
+					break
+				}
+				weight = weight.saturating_sub(T::RepeatVoteDiscount::get().mul_floor(weight));
+			}
+			weight
+		}
+		// Option with the most votes, the lowest index wins ties
+		pub fn get_winning_option(proposal_index: ProposalIndexOf<T>) -> Option<OptionIndex> {
+			let options = Self::get_proposal(proposal_index)?.options.len() as OptionIndex;
+			let mut winner: Option<(OptionIndex, BalanceOf<T>)> = None;
+			for option_index in 0..options {
+				let votes = Self::get_option_votes(proposal_index, option_index);
+				if votes > winner.map_or(0u32.into(), |(_, votes)| votes) {
+					winner = Some((option_index, votes));
+				}
+			}
+			winner.map(|(option_index, _)| option_index)
+		}
+		pub fn get_option_votes(
+			proposal_index: ProposalIndexOf<T>,
+			option_index: OptionIndex,
+		) -> BalanceOf<T> {
+			OptionTally::<T>::get((proposal_index, option_index))
+		}
+		pub fn get_aye_votes_balance(
+			proposal_index: ProposalIndexOf<T>,
+			who: &T::AccountId,
+		) -> BalanceOf<T> {
+			AyeVotes::<T>::get(proposal_index, who)
+		}
+		pub fn get_nay_votes_balance(
+			proposal_index: ProposalIndexOf<T>,
+			who: &T::AccountId,
+		) -> BalanceOf<T> {
+			NayVotes::<T>::get(proposal_index, who)
+		}
+		#[deprecated(note = "Use get_free_voting_balance instead")]
+		pub fn get_voter_balance(who: &T::AccountId) -> BalanceOf<T> {
+			Self::get_free_voting_balance(who)
+		}
+		// Balance the voter can still reserve, the reserved tokens are not part of it
+		pub fn get_free_voting_balance(who: &T::AccountId) -> BalanceOf<T> {
+			T::Currency::total_balance(who) - T::Currency::reserved_balance(who)
+		}
+		// Fees are withdrawn keeping the account alive, so they can't take the existential deposit
+		pub fn can_pay_keeping_alive(who: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			Self::get_free_voting_balance(who) >= amount &&
+				T::Currency::total_balance(who).saturating_sub(amount) >=
+					T::Currency::minimum_balance()
+		}
+		pub fn get_reserved_tokens(who: &T::AccountId) -> BalanceOf<T> {
+			T::Currency::reserved_balance(who)
+		}
+		// Own reserve not lent to other voters nor held as deposits, plus the sponsored tokens
+		pub fn get_votable_reserve(who: &T::AccountId) -> BalanceOf<T> {
+			T::Currency::reserved_balance(who)
+				.saturating_sub(SponsoredOut::<T>::get(who))
+				.saturating_sub(HeldDeposits::<T>::get(who))
+				.saturating_add(SponsoredIn::<T>::get(who))
+		}
+		pub fn get_committed_tokens(who: &T::AccountId) -> BalanceOf<T> {
+			CommittedTokens::<T>::iter_prefix_values(who)
+				.fold(0u32.into(), |acc: BalanceOf<T>, committed| acc.saturating_add(committed))
+		}
+		// Reserve not yet spent on votes, including the one delegated to the voter
+		pub fn get_available_reserve(who: &T::AccountId) -> BalanceOf<T> {
+			Self::get_votable_reserve(who)
+				.saturating_add(Self::get_delegated_reserve(who))
+				.saturating_sub(Self::get_committed_tokens(who))
+		}
+		// Available reserve of every voter delegating to this one, directly or through others
+		pub fn get_delegated_reserve(who: &T::AccountId) -> BalanceOf<T> {
+			Self::delegators_of(who)
+				.iter()
+				.fold(0u32.into(), |acc: BalanceOf<T>, delegator| {
+					acc.saturating_add(
+						Self::get_votable_reserve(delegator)
+							.saturating_sub(Self::get_committed_tokens(delegator)),
+					)
+				})
+		}
+		// Every voter delegating to this one, directly or through others; delegate keeps them
+		// within MaxDelegators
+		pub fn delegators_of(who: &T::AccountId) -> Vec<T::AccountId> {
+			let mut delegators = Delegators::<T>::get(who).into_inner();
+			let mut next = 0;
+			while next < delegators.len() {
+				let indirect = Delegators::<T>::get(&delegators[next]);
+				delegators.extend(indirect);
+				next += 1;
+			}
+			delegators
+		}
+		// Last voter of the delegation chain starting at `who`, the one voting with its reserve
+		pub fn delegation_root(who: &T::AccountId) -> T::AccountId {
+			let mut current = who.clone();
+			while let Some(delegate) = Delegations::<T>::get(&current) {
+				current = delegate;
+			}
+			current
+		}
+		// Moving a delegation is only safe while none of the reserve flowing through it is spent,
+		// otherwise the same tokens could vote twice
+		pub fn ensure_delegated_tokens_uncommitted(who: &T::AccountId) -> DispatchResult {
+			let mut current = Some(who.clone());
+			while let Some(account) = current {
+				ensure!(
+					Self::get_committed_tokens(&account) == 0u32.into(),
+					Error::<T>::DelegatedTokensCommitted
+				);
+				current = Delegations::<T>::get(&account);
+			}
+			Ok(())
+		}
+		// Whether following the delegations from `from` reaches `who`
+		pub fn delegation_leads_to(from: &T::AccountId, who: &T::AccountId) -> bool {
+			let mut current = from.clone();
+			loop {
+				if &current == who {
+					return true
+				}
+				match Delegations::<T>::get(&current) {
+					Some(delegate) => current = delegate,
+					None => return false,
+				}
+			}
+		}
+		// Reserved balance minus the tokens committed to proposals; zero when commitments exceed
+		// the reserve (accounting drift)
+		pub fn reserve_discrepancy(who: &T::AccountId) -> BalanceOf<T> {
+			T::Currency::reserved_balance(who).saturating_sub(Self::get_committed_tokens(who))
+		}
+		// Gives the proposal deposit back to the proposer, or slashes it to the FeeHandler
+		pub fn settle_deposit(proposal: &Proposal<T>, refund: bool) {
+			let deposit = proposal.deposit;
+			if deposit == 0u32.into() {
+				return
+			}
+			let proposal_index = proposal.proposal_index;
+			let proposer = &proposal.proposer;
+			HeldDeposits::<T>::mutate(proposer, |held| *held = held.saturating_sub(deposit));
+			if refund {
+				let amount = deposit.saturating_sub(T::Currency::unreserve(proposer, deposit));
+				Self::deposit_event(Event::ProposalDepositReturned { proposal_index, amount });
+			} else {
+				let (slashed, _) = T::Currency::slash_reserved(proposer, deposit);
+				let amount = slashed.peek();
+				T::FeeHandler::on_unbalanced(slashed);
+				Self::deposit_event(Event::ProposalDepositSlashed { proposal_index, amount });
+			}
+		}
+		// Gives a held start fee back to its payer, or slashes it to the FeeHandler. Returns
+		// whether the proposal had a held fee
+		pub fn settle_start_fee(proposal_index: ProposalIndexOf<T>, refund: bool) -> bool {
+			let (payer, fee) = match HeldStartFees::<T>::take(proposal_index) {
+				Some(held) => held,
+				None => return false,
+			};
+			HeldDeposits::<T>::mutate(&payer, |held| *held = held.saturating_sub(fee));
+			if refund {
+				let amount = fee.saturating_sub(T::Currency::unreserve(&payer, fee));
+				Self::deposit_event(Event::StartFeeRefunded { proposal_index, amount });
+			} else {
+				let (slashed, _) = T::Currency::slash_reserved(&payer, fee);
+				T::FeeHandler::on_unbalanced(slashed);
+			}
+			true
+		}
+		pub fn update_proposal_status_to_completed(
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			let proposal: Proposal<T> =
+				Self::get_proposal(proposal_index).ok_or(Error::<T>::ProposalNotFound)?;
+
+			// The fee is only refunded once, even if the proposal completes again after a dispute
+			let mut start_fee = proposal.start_fee;
+			if Self::settle_start_fee(proposal_index, true) {
+				start_fee = 0u32.into();
+			}
+			if T::AutoUnreserveOnCompletion::get() {
+				Self::refund_committed_tokens(proposal_index);
+			}
+			// Like the fee, the deposit is only settled once
+			Self::settle_deposit(&proposal, Self::quorum_reached(proposal_index));
+			// Rewards are paid once too, the first time the proposal completes as the winner
+			if Self::get_winner() == Some(proposal_index) &&
+				!RewardedProposals::<T>::contains_key(proposal_index)
+			{
+				Self::reward_winner_from_pool(proposal_index, &proposal.proposer);
+				Self::mint_winner_reward(&proposal.proposer);
+				RewardedProposals::<T>::insert(proposal_index, ());
+			}
+
+			// A proposal with more nays than ayes is vetoed rather than merely not winning
+			let (total_ayes, total_nays) = ProposalTally::<T>::get(proposal_index);
+			let status = if total_nays > total_ayes {
+				Self::deposit_event(Event::ProposalVetoed { proposal_index });
+				ProposalStatus::Vetoed
+			} else {
+				ProposalStatus::Completed
+			};
+
+			let proposal_updated: Proposal<T> = Proposal {
+				proposal_index,
+				text: proposal.text,
+				proposer: proposal.proposer,
+				end_block: proposal.end_block,
+				start_block: proposal.start_block,
+				start_fee,
+				created_at: proposal.created_at,
+				completed_at: Some(<frame_system::Pallet<T>>::block_number()),
+				status,
+				options: proposal.options,
+				metadata: proposal.metadata,
+				deposit: 0u32.into(),
+			};
+			Proposals::<T>::mutate_exists(&proposal_index, |p| {
+				*p = if let Some(_) = p { Some(proposal_updated) } else { None }
+			});
+			Self::deposit_event(Event::ProposalCompleted {
+				proposal_index,
+				ayes: total_ayes,
+				nays: total_nays,
+			});
+			Ok(())
+		}
+		// Must run before the vote is stored, so first votes can be told apart
+		pub fn count_proposal_voter(
+			proposal_index: ProposalIndexOf<T>,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			// Option votes leave no Aye, Nay or Abstain entry, so look at the ballots instead
+			if VoterBallots::<T>::get(who).contains(&proposal_index) {
+				return Ok(())
+			}
+			ProposalVoterCount::<T>::try_mutate(proposal_index, |count| -> DispatchResult {
+				ensure!(
+					*count < T::MaxVotersPerProposal::get(),
+					Error::<T>::ProposalVoterCapReached
+				);
+				*count = count.saturating_add(1);
+				Ok(())
+			})?;
+			VoterBallots::<T>::try_mutate(who, |ballots| -> DispatchResult {
+				ballots.try_push(proposal_index).map_err(|_| Error::<T>::TooManyProposals)?;
+				Ok(())
+			})
+		}
+		// (proposal_index, vote, weight) of every ballot cast by the voter
+		pub fn votes_of(who: &T::AccountId) -> Vec<(ProposalIndexOf<T>, Vote, BalanceOf<T>)> {
+			VoterBallots::<T>::get(who)
+				.into_iter()
+				.filter_map(|proposal_index| {
+					if AyeVotes::<T>::contains_key(proposal_index, who) {
+						Some((proposal_index, Vote::Aye, AyeVotes::<T>::get(proposal_index, who)))
+					} else if NayVotes::<T>::contains_key(proposal_index, who) {
+						Some((proposal_index, Vote::Nay, NayVotes::<T>::get(proposal_index, who)))
+					} else if AbstainVotes::<T>::contains_key(proposal_index, who) {
+						Some((proposal_index, Vote::Abstain, 0u32.into()))
+					} else {
+						None
+					}
+				})
+				.collect()
+		}
+		pub fn proposals_voted_by(who: &T::AccountId) -> Vec<ProposalIndexOf<T>> {
+			VotedProposals::<T>::get(who).into_inner()
+		}
+		// Proposals created by the account and not removed since, archived drafts included
+		pub fn proposals_by(who: &T::AccountId) -> Vec<ProposalIndexOf<T>> {
+			ProposerIndex::<T>::get(who).into_inner()
+		}
+		pub fn record_voted_proposal(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+		) -> DispatchResult {
+			VotedProposals::<T>::try_mutate(who, |voted| -> DispatchResult {
+				if voted.contains(&proposal_index) {
+					return Ok(())
+				}
+				voted.try_push(proposal_index).map_err(|_| Error::<T>::TooManyVotedProposals)?;
+				Ok(())
+			})
+		}
+		pub fn reward_pool_account() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+		pub fn reward_pool_balance() -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::reward_pool_account())
+		}
+		pub fn reward_winner_from_pool(
+			proposal_index: ProposalIndexOf<T>,
+			proposer: &T::AccountId,
+		) {
+			let amount = T::PoolReward::get();
+			if amount == 0u32.into() {
+				return
+			}
+			let paid = Self::reward_pool_balance() >= amount &&
+				T::Currency::transfer(
+					&Self::reward_pool_account(),
+					proposer,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				)
+				.is_ok();
+			if paid {
+				Self::deposit_event(Event::WinnerRewarded {
+					proposal_index,
+					who: proposer.clone(),
+					amount,
+				});
+			} else {
+				Self::deposit_event(Event::RewardPoolEmpty { proposal_index });
+			}
+		}
+		pub fn mint_winner_reward(proposer: &T::AccountId) {
+			let reward = T::WinnerReward::get();
+			if reward == 0u32.into() {
+				return
+			}
+			let amount = T::Currency::deposit_creating(proposer, reward).peek();
+			Self::deposit_event(Event::ProposerRewarded { who: proposer.clone(), amount });
+		}
+		// Drop every vote cast by the voter, so none of them counts toward a tally anymore
+		pub fn remove_votes(who: &T::AccountId) {
+			let ballots = VoterBallots::<T>::take(who);
+			for (proposal_index, proposal) in Proposals::<T>::iter() {
+				if ballots.contains(&proposal_index) {
+					ProposalVoterCount::<T>::mutate(proposal_index, |count| {
+						*count = count.saturating_sub(1)
+					});
+				}
+				let ayes = AyeVotes::<T>::take(proposal_index, who);
+				let nays = NayVotes::<T>::take(proposal_index, who);
+				ProposalTally::<T>::mutate(proposal_index, |(total_ayes, total_nays)| {
+					*total_ayes = total_ayes.saturating_sub(ayes);
+					*total_nays = total_nays.saturating_sub(nays);
+				});
+				for option_index in 0..proposal.options.len() as OptionIndex {
+					let votes = OptionVotes::<T>::take((proposal_index, option_index), who);
+					OptionTally::<T>::mutate((proposal_index, option_index), |total| {
+						*total = total.saturating_sub(votes)
+					});
+				}
+				AbstainVotes::<T>::remove(proposal_index, who);
+				VoteCounts::<T>::remove(proposal_index, who);
+			}
+			for (proposal_index, committed) in CommittedTokens::<T>::drain_prefix(who) {
+				ProposalCommittedReserve::<T>::mutate(proposal_index, |reserve| {
+					*reserve = reserve.saturating_sub(committed)
+				});
+			}
+			VotedProposals::<T>::remove(who);
+		}
+		// Give back, without any penalty, the tokens each voter committed to the proposal
+		pub fn refund_committed_tokens(proposal_index: ProposalIndexOf<T>) {
+			let options = Self::get_proposal(proposal_index)
+				.map_or(0, |proposal| proposal.options.len() as OptionIndex);
+			let option_voters = (0..options).flat_map(move |option_index| {
+				OptionVotes::<T>::iter_key_prefix((proposal_index, option_index))
+			});
+			let voters = AyeVotes::<T>::iter_key_prefix(proposal_index)
+				.chain(NayVotes::<T>::iter_key_prefix(proposal_index))
+				.chain(option_voters);
+			for who in voters {
+				// Locked voters claim their refund once the lock expires
+				if Self::locked_reserve(&who) > 0u32.into() {
+					continue
+				}
+				let committed = CommittedTokens::<T>::take(&who, proposal_index);
+				if committed == 0u32.into() {
+					continue
+				}
+				Self::release_committed_tokens(proposal_index, who, committed);
+			}
+		}
+		// Unreserve the tokens taken out of CommittedTokens for the proposal
+		pub fn release_committed_tokens(
+			proposal_index: ProposalIndexOf<T>,
+			who: T::AccountId,
+			committed: BalanceOf<T>,
+		) {
+			let amount = committed.saturating_sub(T::Currency::unreserve(&who, committed));
+			if T::Currency::reserved_balance(&who) == 0u32.into() {
+				ReservedAt::<T>::remove(&who);
+			}
+			VotedProposals::<T>::mutate(&who, |voted| {
+				voted.retain(|index| *index != proposal_index)
+			});
+			Self::deposit_event(Event::VotesRefunded { proposal_index, who, amount });
+		}
+		// Quadratic weight of the tokens used, plus the configured modifiers
+		pub fn vote_weight(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+			tokens: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			Self::vote_weight_at(who, proposal_index, tokens, current_block)
+		}
+		// Same as vote_weight, decayed as if the vote was cast at the given block
+		pub fn vote_weight_at(
+			who: &T::AccountId,
+			proposal_index: ProposalIndexOf<T>,
+			tokens: BalanceOf<T>,
+			at: T::BlockNumber,
+		) -> BalanceOf<T> {
+			let mut weight = tokens.integer_sqrt();
+			if T::AgeWeighting::get() {
+				weight = weight.saturating_mul(Self::age_multiplier(who).into());
+			}
+			weight.saturating_sub(Self::weight_decay(proposal_index, weight, at))
+		}
+		// Part of the weight lost for voting late, proportional to the elapsed share of the window
+		pub fn weight_decay(
+			proposal_index: ProposalIndexOf<T>,
+			weight: BalanceOf<T>,
+			at: T::BlockNumber,
+		) -> BalanceOf<T> {
+			let proposal = match Self::get_proposal(proposal_index) {
+				Some(proposal) => proposal,
+				None => return 0u32.into(),
+			};
+			let window: u128 =
+				proposal.end_block.saturating_sub(proposal.start_block).saturated_into();
+			if window == 0 {
+				return 0u32.into()
+			}
+			let elapsed: u128 = at.saturating_sub(proposal.start_block).saturated_into();
+			let decay = T::WeightDecay::get()
+				.mul_floor(Self::balance_to_u128(weight).saturating_mul(elapsed.min(window))) /
+				window;
+			decay.saturated_into()
+		}
+		// One extra step for every full voting period since registration, capped
+		pub fn age_multiplier(who: &T::AccountId) -> u32 {
+			let voting_period = T::VotingPeriod::get();
+			let registered_at = match RegisteredAt::<T>::get(who) {
+				Some(registered_at) if voting_period != 0u32.into() => registered_at,
+				_ => return 1,
+			};
+			let age = <frame_system::Pallet<T>>::block_number().saturating_sub(registered_at);
+			let steps = (age / voting_period).saturated_into::<u32>();
+			steps.saturating_add(1).min(T::MaxAgeMultiplier::get()).max(1)
+		}
+		// One extra step for every lock period committed to
+		pub fn conviction_multiplier(periods: u32) -> u32 {
+			periods.saturating_add(1)
+		}
+		// Reserved tokens still locked by conviction votes, zero once the lock expired
+		pub fn locked_reserve(who: &T::AccountId) -> BalanceOf<T> {
+			match ReserveLocks::<T>::get(who) {
+				Some((locked, until)) if until > <frame_system::Pallet<T>>::block_number() =>
+					locked,
+				_ => 0u32.into(),
+			}
+		}
+		// Adds the tokens to the voter's lock, which lasts multiplier lock periods at least
+		pub fn lock_reserve(who: &T::AccountId, tokens: BalanceOf<T>, multiplier: u32) {
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			let until = current_block
+				.saturating_add(T::LockPeriodLength::get().saturating_mul(multiplier.into()));
+			let (locked, until) = match ReserveLocks::<T>::get(who) {
+				Some((locked, locked_until)) if locked_until > current_block =>
+					(locked.saturating_add(tokens), locked_until.max(until)),
+				_ => (tokens, until),
+			};
+			ReserveLocks::<T>::insert(who, (locked, until));
+			Self::deposit_event(Event::TokensLocked { who: who.clone(), amount: locked, until });
+		}
 		pub fn checked_sub_between_balances(
 			first_balance: BalanceOf<T>,
 			second_balance: BalanceOf<T>,
@@ -590,21 +3464,334 @@ pub mod pallet {
 		) -> Result<BalanceOf<T>, DispatchError> {
 			first_balance.checked_div(&second_balance).ok_or(Error::<T>::SlashFailed.into())
 		}
-		// Logic to get the winner
-		pub fn get_winner() -> ProposalIndex {
-			let proposal_indexes = Proposals::<T>::iter().map(|(proposal_index, _)| proposal_index);
+		// Logic to get the winner, None if no proposal has positive net votes or the leader
+		// misses the quorum
+		pub fn get_winner() -> Option<ProposalIndexOf<T>> {
+			let leader = Self::get_leader();
+			log::debug!(target: LOG_TARGET, "current leader (index, score): {:?}", leader);
+			leader
+				.map(|(proposal_index, _)| proposal_index)
+				.filter(|proposal_index| Self::quorum_reached(*proposal_index))
+		}
+		// Abstentions count toward the quorum, but only ayes and nays decide the winner
+		pub fn quorum_reached(proposal_index: ProposalIndexOf<T>) -> bool {
+			Self::voter_count(proposal_index) >= T::MinQuorum::get()
+		}
+		// Distinct accounts that voted on the proposal, top ups and vote changes included once
+		pub fn voter_count(proposal_index: ProposalIndexOf<T>) -> u32 {
+			ProposalVoterCount::<T>::get(proposal_index)
+		}
+		// Ayes minus nays, saturating at zero
+		pub fn get_net_votes(proposal_index: ProposalIndexOf<T>) -> u128 {
+			Self::balance_to_u128(Self::total_votes_for_proposal(proposal_index))
+		}
+		// Net votes of a proposal, read from the running tally without going through its voters
+		pub fn total_votes_for_proposal(proposal_index: ProposalIndexOf<T>) -> BalanceOf<T> {
+			let (total_ayes, total_nays) = ProposalTally::<T>::get(proposal_index);
+			total_ayes.saturating_sub(total_nays)
+		}
+		// (ayes, nays) of a registered proposal
+		pub fn proposal_tally(
+			proposal_index: ProposalIndexOf<T>,
+		) -> Option<(BalanceOf<T>, BalanceOf<T>)> {
+			Self::is_proposal_registered(proposal_index)
+				.then(|| ProposalTally::<T>::get(proposal_index))
+		}
+		pub fn add_to_tally(proposal_index: ProposalIndexOf<T>, vote: &Vote, weight: BalanceOf<T>) {
+			ProposalTally::<T>::mutate(proposal_index, |(total_ayes, total_nays)| match vote {
+				Vote::Aye => *total_ayes = total_ayes.saturating_add(weight),
+				Vote::Nay => *total_nays = total_nays.saturating_add(weight),
+				Vote::Abstain => {},
+			});
+		}
+		// Rejected and vetoed proposals can't lead nor be the runner-up
+		pub fn contending_proposals() -> impl Iterator<Item = ProposalIndexOf<T>> {
+			Proposals::<T>::iter()
+				.filter(|(_, proposal)| {
+					!matches!(proposal.status, ProposalStatus::Rejected | ProposalStatus::Vetoed)
+				})
+				.map(|(proposal_index, _)| proposal_index)
+		}
+		// Leading proposal together with its score, if any proposal received votes
+		pub fn get_leader() -> Option<(ProposalIndexOf<T>, u128)> {
+			let proposal_indexes = Self::contending_proposals();
+			let mut leader = None;
 			let mut max_votes = 0u128;
-			let mut winner = 0u32;
 			for proposal_index in proposal_indexes {
-				let total_votes: u128 = AyeVotes::<T>::iter_prefix(proposal_index)
-					.map(|(_, aye_votes)| Self::balance_to_u128(aye_votes))
-					.sum();
+				let total_votes = Self::get_net_votes(proposal_index);
+				log::trace!(target: LOG_TARGET, "proposal {:?} tally: {}", proposal_index, total_votes);
+				if total_votes > max_votes {
+					max_votes = total_votes;
+					leader = Some((proposal_index, total_votes));
+				}
+			}
+			leader
+		}
+		// Share of the (sampled) proposal weight held by its top voter
+		pub fn influence_concentration(proposal_index: ProposalIndexOf<T>) -> Percent {
+			let (top, total) = AyeVotes::<T>::iter_prefix_values(proposal_index)
+				.take(T::MaxConcentrationVoters::get() as usize)
+				.map(Self::balance_to_u128)
+				.fold((0u128, 0u128), |(top, total), weight| {
+					(top.max(weight), total.saturating_add(weight))
+				});
+			if total == 0 {
+				return Percent::zero()
+			}
+			Percent::from_rational(top, total)
+		}
+		pub fn get_runner_up() -> Option<(ProposalIndexOf<T>, u128)> {
+			let (leader_index, _) = Self::get_leader()?;
+			let mut runner_up = None;
+			let mut max_votes = 0u128;
+			for proposal_index in Self::contending_proposals() {
+				if proposal_index == leader_index {
+					continue
+				}
+				let total_votes = Self::get_net_votes(proposal_index);
 				if total_votes > max_votes {
 					max_votes = total_votes;
-					winner = proposal_index;
+					runner_up = Some((proposal_index, total_votes));
+				}
+			}
+			runner_up
+		}
+		// The runner-up, when the leader doesn't outpace it by WinMargin
+		pub fn get_close_runner_up() -> Option<ProposalIndexOf<T>> {
+			let (_, leader_votes) = Self::get_leader()?;
+			let (runner_up, runner_up_votes) = Self::get_runner_up()?;
+			let required_votes =
+				runner_up_votes.saturating_add(T::WinMargin::get().mul_floor(runner_up_votes));
+			if leader_votes < required_votes {
+				Some(runner_up)
+			} else {
+				None
+			}
+		}
+		pub fn deposit_voting_ended(winner: Option<ProposalIndexOf<T>>) {
+			Self::deposit_event(Event::ProposalResults { results: Self::proposal_results() });
+			// A leader missing the quorum leaves the voting without a winner
+			if let Some((proposal_index, _)) = Self::get_leader() {
+				if winner.is_none() && !Self::quorum_reached(proposal_index) {
+					Self::deposit_event(Event::VotingEndedNoQuorum { proposal_index });
+					return
+				}
+			}
+			match (winner, Self::get_close_runner_up()) {
+				(Some(leader), Some(runner_up)) =>
+					Self::deposit_event(Event::InconclusiveResult { leader, runner_up }),
+				(None, _) => Self::deposit_event(Event::VotingEndedWithoutWinner),
+				_ => Self::deposit_event(Event::VotingEnded { winner }),
+			}
+		}
+		// In progress proposals by index, at most MaxProposals of them
+		pub fn active_proposals() -> Vec<ProposalIndexOf<T>> {
+			let mut proposal_indexes: Vec<ProposalIndexOf<T>> = Proposals::<T>::iter()
+				.filter(|(_, proposal)| proposal.status == ProposalStatus::InProgress)
+				.map(|(proposal_index, _)| proposal_index)
+				.collect();
+			proposal_indexes.sort();
+			proposal_indexes.truncate(T::MaxProposals::get() as usize);
+			proposal_indexes
+		}
+		// Whether a vote on the proposal would be accepted right now: in progress, not past
+		// its end block (even if it hasn't been completed yet) and the pallet not paused
+		pub fn is_voting_open(proposal_index: ProposalIndexOf<T>) -> bool {
+			!Self::is_paused() &&
+				Self::is_proposal_active(proposal_index) &&
+				Self::is_voting_period_over(proposal_index) == Ok(false)
+		}
+		// (proposal_index, ayes, nays, abstains) of the first MaxProposals proposals by index
+		pub fn proposal_results(
+		) -> BoundedVec<(ProposalIndexOf<T>, BalanceOf<T>, BalanceOf<T>, u32), T::MaxProposals>
+		{
+			let mut proposal_indexes: Vec<ProposalIndexOf<T>> =
+				Proposals::<T>::iter_keys().collect();
+			proposal_indexes.sort();
+			let results: Vec<(ProposalIndexOf<T>, BalanceOf<T>, BalanceOf<T>, u32)> =
+				proposal_indexes
+					.into_iter()
+					.take(T::MaxProposals::get() as usize)
+					.map(|proposal_index| {
+						let (ayes, nays) = ProposalTally::<T>::get(proposal_index);
+						let abstains =
+							AbstainVotes::<T>::iter_key_prefix(proposal_index).count() as u32;
+						(proposal_index, ayes, nays, abstains)
+					})
+					.collect();
+			BoundedVec::try_from(results).unwrap_or_default()
+		}
+		pub fn election_result() -> ElectionResult<T> {
+			let mut tallies: Vec<(ProposalIndexOf<T>, u128)> = Proposals::<T>::iter_keys()
+				.map(|proposal_index| (proposal_index, Self::get_net_votes(proposal_index)))
+				.collect();
+			tallies.sort_by_key(|(proposal_index, _)| *proposal_index);
+			let turnout = tallies.iter().fold(0u32, |acc, (proposal_index, _)| {
+				acc.saturating_add(Self::voter_count(*proposal_index))
+			});
+			// Same quorum as the one deposit_voting_ended applies
+			let winner = Self::get_leader()
+				.filter(|(proposal_index, _)| Self::quorum_reached(*proposal_index));
+			let finalized_at = winner
+				.and_then(|(proposal_index, _)| Self::get_proposal(proposal_index))
+				.and_then(|proposal| proposal.completed_at);
+			let runner_up = Self::get_runner_up();
+			ElectionResult { winner, runner_up, tallies, turnout, finalized_at }
+		}
+		pub fn get_winner_outcome() -> WinnerOutcome<ProposalIndexOf<T>> {
+			match Self::get_leader() {
+				Some((proposal_index, score)) if !Self::quorum_reached(proposal_index) =>
+					WinnerOutcome::NoQuorum(proposal_index, score),
+				Some((proposal_index, score)) if score >= T::PassThreshold::get() =>
+					WinnerOutcome::Winner(proposal_index, score),
+				Some((proposal_index, score)) => WinnerOutcome::NoPass(proposal_index, score),
+				None => WinnerOutcome::NoVotes,
+			}
+		}
+		// Remove the proposals not started within ProposalStartDeadline blocks of their creation,
+		// as long as the idle weight allows it
+		pub fn expire_unstarted_proposals(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let deadline = T::ProposalStartDeadline::get();
+			if deadline == 0u32.into() {
+				return Weight::zero()
+			}
+
+			let db_weight = T::DbWeight::get();
+			let mut weight = Weight::zero();
+			let mut expired = Vec::new();
+			for proposal in Proposals::<T>::iter_values() {
+				let cost = db_weight.reads_writes(1, 1);
+				if weight.saturating_add(cost).any_gt(remaining_weight) {
+					break
+				}
+				weight = weight.saturating_add(db_weight.reads(1));
+				if proposal.status == ProposalStatus::NotStarted &&
+					proposal.created_at.saturating_add(deadline) <= n
+				{
+					weight = weight.saturating_add(db_weight.writes(1));
+					expired.push(proposal.proposal_index);
+				}
+			}
+
+			// The deposit is given back, the proposal was never put to a vote
+			for proposal_index in expired {
+				if let Some(proposal) = Proposals::<T>::take(proposal_index) {
+					Self::settle_deposit(&proposal, true);
+					ProposalsByProposer::<T>::mutate(&proposal.proposer, |count| {
+						*count = count.saturating_sub(1)
+					});
+					ProposerIndex::<T>::mutate(&proposal.proposer, |indexes| {
+						indexes.retain(|index| *index != proposal_index)
+					});
+				}
+				ProposalTexts::<T>::remove(proposal_index);
+				CustomVotingPeriods::<T>::remove(proposal_index);
+				Self::deposit_event(Event::ProposalExpiredUnstarted { proposal_index });
+			}
+			weight
+		}
+		// Proposals stay open for reveals until RevealPeriod blocks after their end block
+		pub fn completion_block(end_block: T::BlockNumber) -> T::BlockNumber {
+			end_block.saturating_add(T::RevealPeriod::get())
+		}
+		// Proposals already past their completion block complete at the end of the current one.
+		// Blocks already holding MaxCompletionsPerBlock expiries push the proposal to a later one
+		pub fn schedule_expiry(proposal_index: ProposalIndexOf<T>, end_block: T::BlockNumber) {
+			let max_completions = T::MaxCompletionsPerBlock::get().max(1);
+			let mut at =
+				Self::completion_block(end_block).max(<frame_system::Pallet<T>>::block_number());
+			while ExpiringCount::<T>::get(at) >= max_completions {
+				at = at.saturating_add(1u32.into());
+			}
+			if !ExpiringProposals::<T>::contains_key(at, proposal_index) {
+				ExpiringProposals::<T>::insert(at, proposal_index, ());
+				ExpiringCount::<T>::mutate(at, |count| *count = count.saturating_add(1));
+			}
+		}
+		// Weight of the completions on_finalize runs at the block, reserved by on_initialize
+		pub fn expiry_weight(n: T::BlockNumber) -> Weight {
+			let expiring = ExpiringCount::<T>::get(n);
+			T::DbWeight::get().reads(2).saturating_add(
+				T::WeightInfo::complete_proposal(Proposals::<T>::count())
+					.saturating_mul(expiring.into()),
+			)
+		}
+		// Entries whose proposal was completed or reopened in the meantime are skipped
+		pub fn complete_expiring_proposals(n: T::BlockNumber) {
+			ExpiringCount::<T>::remove(n);
+			for (proposal_index, _) in ExpiringProposals::<T>::drain_prefix(n) {
+				let expired = Self::get_proposal(proposal_index).map_or(false, |proposal| {
+					proposal.status == ProposalStatus::InProgress &&
+						Self::completion_block(proposal.end_block) <= n
+				});
+				if expired && Self::update_proposal_status_to_completed(proposal_index).is_ok() {
+					Self::deposit_voting_ended(Self::get_winner());
+				}
+			}
+		}
+		// A yield round starts every YieldPeriod blocks and credits at most
+		// MaxYieldPayoutsPerBlock voters per block until every voter has been visited
+		pub fn accrue_reserve_yield(n: T::BlockNumber) -> Weight {
+			let reserve_yield = T::ReserveYield::get();
+			let yield_period = T::YieldPeriod::get();
+			if reserve_yield == Percent::from_percent(0) || yield_period == 0u32.into() {
+				return Weight::zero()
+			}
+
+			let db_weight = T::DbWeight::get();
+			let mut weight = db_weight.reads(1);
+			if n % yield_period == 0u32.into() {
+				YieldCursor::<T>::put(BoundedVec::default());
+				weight = weight.saturating_add(db_weight.writes(1));
+			}
+			let cursor = match YieldCursor::<T>::get() {
+				Some(cursor) => cursor,
+				None => return weight,
+			};
+
+			let voters = if cursor.is_empty() {
+				RegisteredVoters::<T>::iter_keys()
+			} else {
+				RegisteredVoters::<T>::iter_keys_from(cursor.into_inner())
+			};
+			let max_payouts = T::MaxYieldPayoutsPerBlock::get();
+			let mut processed = 0u32;
+			let mut last_key = Vec::new();
+			for who in voters.take(max_payouts as usize) {
+				processed += 1;
+				last_key = RegisteredVoters::<T>::hashed_key_for(&who);
+				weight = weight.saturating_add(db_weight.reads(1));
+
+				let amount = reserve_yield.mul_floor(T::Currency::reserved_balance(&who));
+				if amount == 0u32.into() {
+					continue
+				}
+				let _ = T::Currency::deposit_creating(&who, amount);
+				if T::Currency::reserve(&who, amount).is_ok() {
+					Self::deposit_event(Event::YieldAccrued { who, amount });
 				}
+				weight = weight.saturating_add(db_weight.writes(2));
 			}
-			winner
+
+			// The round is over once a page wasn't filled up
+			match BoundedVec::<u8, ConstU32<128>>::try_from(last_key) {
+				Ok(last_key) if processed == max_payouts => YieldCursor::<T>::put(last_key),
+				_ => YieldCursor::<T>::kill(),
+			}
+			weight.saturating_add(db_weight.writes(1))
+		}
+		// The proposal index is derived from the counter, so it must match the stored entries
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			ensure!(
+				Proposals::<T>::count() as usize == Proposals::<T>::iter().count(),
+				"Proposals counter doesn't match the number of stored proposals"
+			);
+			ensure!(
+				Drafts::<T>::count() as usize == Drafts::<T>::iter().count(),
+				"Drafts counter doesn't match the number of stored drafts"
+			);
+			Ok(())
 		}
 		pub fn balance_to_u128(balance: BalanceOf<T>) -> u128 {
 			balance.saturated_into::<u128>()
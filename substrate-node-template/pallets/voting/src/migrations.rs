@@ -0,0 +1,397 @@
+//! Storage migrations for the voting pallet.
+
+pub mod v1 {
+	use crate::{AccountIdOf, Config, Pallet, Proposal, ProposalStatus, Proposals};
+	use frame_support::{
+		pallet_prelude::*, sp_runtime::traits::Saturating, traits::OnRuntimeUpgrade,
+	};
+
+	/// Layout of a proposal before the lifecycle fields were added.
+	#[derive(Decode)]
+	struct OldProposal<T: Config> {
+		proposal_index: u32,
+		text: T::Hash,
+		proposer: AccountIdOf<T>,
+		end_block: T::BlockNumber,
+		status: ProposalStatus,
+	}
+
+	/// Adds the start, fee and lifecycle fields to every stored proposal.
+	///
+	/// The old layout doesn't record when a proposal was created or started, so both are
+	/// derived from `end_block - VotingPeriod` (the proposal's creation block).
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Proposals::<T>::translate::<OldProposal<T>, _>(|_, old| {
+				translated += 1;
+				let created_at = old.end_block.saturating_sub(T::VotingPeriod::get());
+				let completed_at = match old.status {
+					ProposalStatus::Completed => Some(old.end_block),
+					_ => None,
+				};
+				Some(Proposal {
+					proposal_index: old.proposal_index.into(),
+					text: old.text,
+					proposer: old.proposer,
+					end_block: old.end_block,
+					start_block: created_at,
+					start_fee: 0u32.into(),
+					created_at,
+					completed_at,
+					status: old.status,
+					options: Default::default(),
+					metadata: None,
+					deposit: Default::default(),
+				})
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+	}
+}
+
+pub mod v2 {
+	use crate::{AyeVotes, Config, NayVotes, Pallet, ProposalTally};
+	use frame_support::{
+		pallet_prelude::*, sp_runtime::traits::Saturating, traits::OnRuntimeUpgrade,
+	};
+
+	/// Builds the running `ProposalTally` of every proposal from the stored votes.
+	///
+	/// Tallies are only written by votes cast after v2, so they start out empty.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 0u64;
+			for (proposal_index, _, ayes) in AyeVotes::<T>::iter() {
+				reads += 1;
+				ProposalTally::<T>::mutate(proposal_index, |(total_ayes, _)| {
+					*total_ayes = total_ayes.saturating_add(ayes)
+				});
+			}
+			for (proposal_index, _, nays) in NayVotes::<T>::iter() {
+				reads += 1;
+				ProposalTally::<T>::mutate(proposal_index, |(_, total_nays)| {
+					*total_nays = total_nays.saturating_add(nays)
+				});
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(reads + 1, reads + 1)
+		}
+	}
+}
+
+pub mod v3 {
+	use crate::{
+		AccountIdOf, BalanceOf, Config, Pallet, Proposal, ProposalIndexOf, ProposalStatus,
+		Proposals,
+	};
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	/// Layout of a proposal before the options were added.
+	#[derive(Decode)]
+	struct OldProposal<T: Config> {
+		proposal_index: ProposalIndexOf<T>,
+		text: T::Hash,
+		proposer: AccountIdOf<T>,
+		end_block: T::BlockNumber,
+		start_block: T::BlockNumber,
+		start_fee: BalanceOf<T>,
+		created_at: T::BlockNumber,
+		completed_at: Option<T::BlockNumber>,
+		status: ProposalStatus,
+	}
+
+	/// Gives every stored proposal an empty list of options, so they stay binary proposals.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 2 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Proposals::<T>::translate::<OldProposal<T>, _>(|_, old| {
+				translated += 1;
+				Some(Proposal {
+					proposal_index: old.proposal_index,
+					text: old.text,
+					proposer: old.proposer,
+					end_block: old.end_block,
+					start_block: old.start_block,
+					start_fee: old.start_fee,
+					created_at: old.created_at,
+					completed_at: old.completed_at,
+					status: old.status,
+					options: Default::default(),
+					metadata: None,
+					deposit: Default::default(),
+				})
+			});
+			StorageVersion::new(3).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+	}
+}
+
+pub mod v4 {
+	use crate::{
+		AccountIdOf, BalanceOf, Config, Pallet, Proposal, ProposalIndexOf, ProposalStatus,
+		Proposals,
+	};
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	/// Layout of a proposal before the metadata was added.
+	#[derive(Decode)]
+	struct OldProposal<T: Config> {
+		proposal_index: ProposalIndexOf<T>,
+		text: T::Hash,
+		proposer: AccountIdOf<T>,
+		end_block: T::BlockNumber,
+		start_block: T::BlockNumber,
+		start_fee: BalanceOf<T>,
+		created_at: T::BlockNumber,
+		completed_at: Option<T::BlockNumber>,
+		status: ProposalStatus,
+		options: BoundedVec<T::Hash, T::MaxOptions>,
+	}
+
+	/// Stores every proposal without metadata.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 3 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Proposals::<T>::translate::<OldProposal<T>, _>(|_, old| {
+				translated += 1;
+				Some(Proposal {
+					proposal_index: old.proposal_index,
+					text: old.text,
+					proposer: old.proposer,
+					end_block: old.end_block,
+					start_block: old.start_block,
+					start_fee: old.start_fee,
+					created_at: old.created_at,
+					completed_at: old.completed_at,
+					status: old.status,
+					options: old.options,
+					metadata: None,
+					deposit: Default::default(),
+				})
+			});
+			StorageVersion::new(4).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+	}
+}
+
+pub mod v5 {
+	use crate::{
+		AccountIdOf, BalanceOf, Config, Pallet, Proposal, ProposalIndexOf, ProposalStatus,
+		Proposals,
+	};
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	/// Layout of a proposal before the deposit was added.
+	#[derive(Decode)]
+	struct OldProposal<T: Config> {
+		proposal_index: ProposalIndexOf<T>,
+		text: T::Hash,
+		proposer: AccountIdOf<T>,
+		end_block: T::BlockNumber,
+		start_block: T::BlockNumber,
+		start_fee: BalanceOf<T>,
+		created_at: T::BlockNumber,
+		completed_at: Option<T::BlockNumber>,
+		status: ProposalStatus,
+		options: BoundedVec<T::Hash, T::MaxOptions>,
+		metadata: Option<BoundedVec<u8, T::MaxMetadataLen>>,
+	}
+
+	/// Stores every proposal without deposit, none was taken before.
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 4 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Proposals::<T>::translate::<OldProposal<T>, _>(|_, old| {
+				translated += 1;
+				Some(Proposal {
+					proposal_index: old.proposal_index,
+					text: old.text,
+					proposer: old.proposer,
+					end_block: old.end_block,
+					start_block: old.start_block,
+					start_fee: old.start_fee,
+					created_at: old.created_at,
+					completed_at: old.completed_at,
+					status: old.status,
+					options: old.options,
+					metadata: old.metadata,
+					deposit: Default::default(),
+				})
+			});
+			StorageVersion::new(5).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+	}
+}
+
+pub mod v6 {
+	use crate::{Config, OptionTally, OptionVotes, Pallet};
+	use frame_support::{
+		pallet_prelude::*, sp_runtime::traits::Saturating, traits::OnRuntimeUpgrade,
+	};
+
+	/// Builds the running `OptionTally` of every option from the stored option votes.
+	///
+	/// Option totals used to be summed over every voter of the option on each read.
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 5 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 0u64;
+			for (option, _, votes) in OptionVotes::<T>::iter() {
+				reads += 1;
+				OptionTally::<T>::mutate(option, |total| *total = total.saturating_add(votes));
+			}
+			StorageVersion::new(6).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(reads + 1, reads + 1)
+		}
+	}
+}
+
+pub mod v7 {
+	use crate::{Config, Delegations, Delegators, Pallet};
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	/// Builds the `Delegators` reverse index from the stored delegations.
+	///
+	/// Delegates beyond MaxDelegators direct voters keep only the first ones in the index.
+	pub struct MigrateToV7<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 6 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 0u64;
+			for (delegator, delegate) in Delegations::<T>::iter() {
+				reads += 1;
+				Delegators::<T>::mutate(delegate, |delegators| {
+					let _ = delegators.try_push(delegator);
+				});
+			}
+			StorageVersion::new(7).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(reads + 1, reads + 1)
+		}
+	}
+}
+
+pub mod v8 {
+	use crate::{Config, ExpiringCount, ExpiringProposals, Pallet, ProposalStatus, Proposals};
+	use frame_support::{inherent::Vec, pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+	/// Counts the proposals already scheduled to complete at each block, and schedules the
+	/// proposals in progress that were started before the expiry hook existed.
+	///
+	/// Blocks scheduled before v8 may hold more than MaxCompletionsPerBlock proposals, the newly
+	/// scheduled ones honour it.
+	pub struct MigrateToV8<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 7 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 0u64;
+			let mut writes = 0u64;
+			let mut scheduled = Vec::new();
+			for (block, proposal_index, _) in ExpiringProposals::<T>::iter() {
+				reads += 1;
+				writes += 1;
+				ExpiringCount::<T>::mutate(block, |count| *count = count.saturating_add(1));
+				scheduled.push(proposal_index);
+			}
+			for proposal in Proposals::<T>::iter_values() {
+				reads += 1;
+				if proposal.status == ProposalStatus::InProgress &&
+					!scheduled.contains(&proposal.proposal_index)
+				{
+					writes += 2;
+					Pallet::<T>::schedule_expiry(proposal.proposal_index, proposal.end_block);
+				}
+			}
+			StorageVersion::new(8).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+		}
+	}
+}
+
+pub mod v9 {
+	use crate::{Config, Drafts, NextProposalIndex, Pallet, Proposals};
+	use frame_support::{
+		pallet_prelude::*, sp_runtime::traits::Saturating, traits::OnRuntimeUpgrade,
+	};
+
+	/// Moves `NextProposalIndex` past every stored proposal and draft.
+	///
+	/// The index used to fall back to the number of stored proposals, which could point at a
+	/// proposal still in use once others were removed.
+	pub struct MigrateToV9<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV9<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 8 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 1u64;
+			let mut next_proposal_index = NextProposalIndex::<T>::get();
+			for proposal_index in Proposals::<T>::iter_keys().chain(Drafts::<T>::iter_keys()) {
+				reads += 1;
+				next_proposal_index =
+					next_proposal_index.max(proposal_index.saturating_add(1u32.into()));
+			}
+			NextProposalIndex::<T>::put(next_proposal_index);
+			StorageVersion::new(9).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(reads + 1, 2)
+		}
+	}
+}
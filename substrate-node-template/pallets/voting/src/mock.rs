@@ -1,17 +1,20 @@
 use crate as pallet_voting;
 use frame_support::{
 	parameter_types,
-	traits::{ConstU16, ConstU64},
+	traits::{ConstU16, ConstU32, ConstU64, GenesisBuild, Imbalance, OnUnbalanced},
+	PalletId,
 };
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Percent,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 type Balance = u128;
+pub type ProposalIndex = u32;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -56,6 +59,7 @@ impl frame_system::Config for Test {
 parameter_types! {
 	pub const ExistentialDeposit: u64 = 1;
 	pub const MaxLocks: u32 = 10;
+	pub const VotingPalletId: PalletId = PalletId(*b"py/votng");
 }
 impl pallet_balances::Config for Test {
 	type Balance = u128;
@@ -69,13 +73,126 @@ impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+	pub static VotingPeriod: u64 = 100;
+	pub static RequirePreReserve: bool = false;
+	pub static RegistrationIdempotent: bool = false;
+	pub static MinProposalsToVote: u32 = 0;
+	pub static PassThreshold: u128 = 0;
+	pub static ReserveYield: Percent = Percent::from_percent(0);
+	pub static MaxYieldPayoutsPerBlock: u32 = 10;
+	pub static AgeWeighting: bool = false;
+	pub static RefundStartFee: bool = false;
+	pub static WeightDecay: Percent = Percent::from_percent(0);
+	pub static AllowRepeatVotes: bool = false;
+	pub static RepeatVoteDiscount: Percent = Percent::from_percent(0);
+	pub static AutoUnreserveOnCompletion: bool = false;
+	pub static MaxProposalReserve: u64 = u64::MAX;
+	pub static WinMargin: Percent = Percent::from_percent(0);
+	pub static MaxVotersPerProposal: u32 = u32::MAX;
+	pub static ProposalStartDeadline: u64 = 0;
+	pub static RejectEmptyText: bool = false;
+	pub static PoolReward: u128 = 0;
+	pub static MinQuorum: u32 = 0;
+	pub static WinnerReward: u128 = 0;
+	pub static RoutedFees: u128 = 0;
+	pub static MaxProposals: u32 = u32::MAX;
+	pub static AllowSelfVoting: bool = true;
+	pub static MaxVotesPerVoter: u32 = u32::MAX;
+	pub static MinReserveAmount: u128 = 1;
+	pub static ProposalCreationFee: u128 = 0;
+	pub static ProposalDeposit: u128 = 0;
+	pub static MinProposerReserve: u128 = 0;
+	pub static MaxDelegators: u32 = 100;
+	pub static RevealPeriod: u64 = 0;
+	pub static MaxCompletionsPerBlock: u32 = 10;
+}
+
+// Keeps the total of the fees and slashes routed by the pallet
+pub struct RecordFees;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for RecordFees {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		RoutedFees::set(&(RoutedFees::get() + amount.peek()));
+	}
+}
+
 impl pallet_voting::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
-	type VotingPeriod = ConstU64<100>;
+	type VotingPeriod = VotingPeriod;
+	type RequirePreReserve = RequirePreReserve;
+	type RegistrationIdempotent = RegistrationIdempotent;
+	type MinProposalsToVote = MinProposalsToVote;
+	type PassThreshold = PassThreshold;
+	type DisputeWindow = ConstU64<10>;
+	type ReserveYield = ReserveYield;
+	type YieldPeriod = ConstU64<10>;
+	type MaxYieldPayoutsPerBlock = MaxYieldPayoutsPerBlock;
+	type AgeWeighting = AgeWeighting;
+	type MaxAgeMultiplier = ConstU32<4>;
+	type RefundStartFee = RefundStartFee;
+	type WeightDecay = WeightDecay;
+	type LockPeriodLength = ConstU64<10>;
+	type MaxLockPeriods = ConstU32<6>;
+	type AllowRepeatVotes = AllowRepeatVotes;
+	type RepeatVoteDiscount = RepeatVoteDiscount;
+	type MaxConcentrationVoters = ConstU32<100>;
+	type AutoUnreserveOnCompletion = AutoUnreserveOnCompletion;
+	type MaxProposalReserve = MaxProposalReserve;
+	type MaxVoted = ConstU32<10>;
+	type WinMargin = WinMargin;
+	type MaxVotersPerProposal = MaxVotersPerProposal;
+	type MaxBulkStatusAccounts = ConstU32<3>;
+	type ProposalStartDeadline = ProposalStartDeadline;
+	type RejectEmptyText = RejectEmptyText;
+	type PalletId = VotingPalletId;
+	type PoolReward = PoolReward;
+	type MaxProposalTextLen = ConstU32<64>;
+	type MinQuorum = MinQuorum;
+	type WinnerReward = WinnerReward;
+	type FeeHandler = RecordFees;
+	type MaxProposals = MaxProposals;
+	type MinVotingPeriod = ConstU64<10>;
+	type MaxVotingPeriod = ConstU64<1_000>;
+	type AllowSelfVoting = AllowSelfVoting;
+	type MaxBatchSize = ConstU32<10>;
+	type ProposalIndex = ProposalIndex;
+	type MaxVotesPerVoter = MaxVotesPerVoter;
+	type MaxOptions = ConstU32<3>;
+	type MinReserveAmount = MinReserveAmount;
+	type MaxMetadataLen = ConstU32<32>;
+	type ProposalCreationFee = ProposalCreationFee;
+	type ProposalDeposit = ProposalDeposit;
+	type MinProposerReserve = MinProposerReserve;
+	type MaxDelegators = MaxDelegators;
+	type RevealPeriod = RevealPeriod;
+	type MaxCompletionsPerBlock = MaxCompletionsPerBlock;
+	type WeightInfo = ();
 }
 
+// Tests rely on the proposal indexes of the mock being plain u32
+const _: fn(u32) -> pallet_voting::ProposalIndexOf<Test> = |proposal_index| proposal_index;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
 }
+
+// Same as new_test_ext, with a reveal period for the benchmarks to reveal hidden votes in and
+// bounds low enough for their components to be run up to them.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_bench_ext() -> sp_io::TestExternalities {
+	RevealPeriod::set(&10);
+	MaxProposals::set(&100);
+	MaxVotersPerProposal::set(&100);
+	new_test_ext()
+}
+
+// Same as new_test_ext, with the given (voter, fee) pairs registered at genesis.
+pub fn new_test_ext_with_voters(voters: Vec<(u64, Balance)>) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_voting::GenesisConfig::<Test> { voters }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	storage.into()
+}
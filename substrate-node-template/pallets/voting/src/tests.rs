@@ -1,8 +1,25 @@
-use crate::{mock::*, AccountIdOf, BalanceOf, Error, Event, ProposalIndex, ProposalStatus, Vote};
+use crate::{
+	migrations, mock::*, weights::WeightInfo, AbstainVotes, AccountIdOf, AyeVotes, BalanceOf,
+	CommittedTokens, ConvictionLocks, CustomVotingPeriods, Delegations, Delegators, Drafts,
+	ElectionResult, Error, Event, ExpiringCount, ExpiringProposals, HeldDeposits, NayVotes,
+	NextProposalIndex, OptionVotes, ProposalCommittedReserve, ProposalStatus, ProposalTally,
+	ProposalTexts, ProposalTimeline, ProposalVoterCount, Proposals, ProposalsByProposer,
+	ProposerIndex, ReserveLocks, SponsoredIn, SponsoredOut, Sponsorships, Vote, VoteCommitments,
+	VoteCounts, WinnerOutcome, EMPTY_TEXT_HASH,
+};
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	traits::{
+		Currency, GetStorageVersion, OnFinalize, OnIdle, OnInitialize, OnRuntimeUpgrade,
+		ReservableCurrency, StorageVersion,
+	},
+	weights::Weight,
+};
 use frame_system::RawOrigin;
 use sp_core::{blake2_256, H256};
+use sp_runtime::Percent;
 
 #[test]
 fn register_voter() {
@@ -17,11 +34,13 @@ fn register_voter() {
 		assert_ok!(setup_new_voter(voter_id, fee));
 		// Check initial balance
 		let balance = 100 - fee;
-		assert_eq!(Voting::get_voter_balance(&voter_id), balance);
+		assert_eq!(Voting::get_free_voting_balance(&voter_id), balance);
 		// Read pallet storage and assert an expected result.
 		assert_eq!(Voting::is_voter_registered(&voter_id), true);
 		// Check event
-		System::assert_last_event((Event::VoterRegistered { voter_id, initial_balance: balance }).into());
+		System::assert_last_event(
+			(Event::VoterRegistered { voter_id, initial_balance: balance }).into(),
+		);
 	});
 }
 
@@ -53,7 +72,7 @@ fn try_register_voter_already_registered() {
 }
 
 #[test]
-fn try_register_voter_with_overflow_fee(){
+fn try_register_voter_with_overflow_fee() {
 	new_test_ext().execute_with(|| {
 		// Go past genesis block so events get deposited
 		System::set_block_number(1);
@@ -66,8 +85,6 @@ fn try_register_voter_with_overflow_fee(){
 	});
 }
 
-
-
 #[test]
 fn reserve_tokens() {
 	new_test_ext().execute_with(|| {
@@ -76,20 +93,20 @@ fn reserve_tokens() {
 		assert_ok!(setup_new_voter(voter_id, fee));
 		// Read pallet storage and assert an expected result.
 		let balance = 100 - fee;
-		assert_eq!(Voting::get_voter_balance(&voter_id), balance);
+		assert_eq!(Voting::get_free_voting_balance(&voter_id), balance);
 		// Dispatch a signed extrinsic.
 		let reserved_tokens = 50;
 		assert_ok!(reserve_tokens_helper(voter_id, reserved_tokens));
 		// Read pallet storage and assert an expected result.
 		let final_balance = balance - reserved_tokens;
-		assert_eq!(Voting::get_voter_balance(&voter_id), final_balance);
+		assert_eq!(Voting::get_free_voting_balance(&voter_id), final_balance);
 		// Check event
 		System::assert_last_event((Event::TokensReserved { who: 1, amount: 50 }).into());
 	});
 }
 
 #[test]
-fn try_to_reserve_tokens_with_not_registered_voter(){
+fn try_to_reserve_tokens_with_not_registered_voter() {
 	new_test_ext().execute_with(|| {
 		// Read pallet storage and assert an expected result.
 		assert_eq!(Voting::is_voter_registered(&1), false);
@@ -106,7 +123,7 @@ fn try_to_reserve_empty_amount_of_tokens() {
 		assert_ok!(setup_new_voter(1, fee));
 		// Read pallet storage and assert an expected result.
 		let balance = 100 - fee;
-		assert_eq!(Voting::get_voter_balance(&1), balance);
+		assert_eq!(Voting::get_free_voting_balance(&1), balance);
 		// Dispatch a signed extrinsic.
 		let reserved_tokens = 0;
 		assert_noop!(
@@ -123,27 +140,25 @@ fn try_to_reserve_more_tokens_than_balanced() {
 		assert_ok!(setup_new_voter(1, fee));
 		// Read pallet storage and assert an expected result.
 		let balance = 100 - fee;
-		assert_eq!(Voting::get_voter_balance(&1), balance);
+		assert_eq!(Voting::get_free_voting_balance(&1), balance);
 		// Reserve more tokens than the balance
 		let reserved_tokens = balance + 1;
 		assert_noop!(reserve_tokens_helper(1, reserved_tokens), Error::<Test>::NotEnoughBalance);
 	});
 }
 
-
-
 #[test]
 fn unreserve_tokens() {
 	new_test_ext().execute_with(|| {
 		let voter: AccountIdOf<Test> = 1;
 		assert_ok!(setup_new_voter(voter, 5));
 		let reserved_tokens = 50;
-		let balance_before_reserve = Voting::get_voter_balance(&voter);
+		let balance_before_reserve = Voting::get_free_voting_balance(&voter);
 		assert_ok!(reserve_tokens_helper(voter, reserved_tokens));
 		assert_ok!(unreserve_tokens_helper(voter, reserved_tokens));
 		// Punishment for unreserving tokens is 50% of the reserved tokens (slash)
 		let balance_after_reserve = balance_before_reserve - reserved_tokens / 2;
-		assert_eq!(Voting::get_voter_balance(&1), balance_after_reserve);
+		assert_eq!(Voting::get_free_voting_balance(&1), balance_after_reserve);
 		// Check event
 		System::assert_last_event(
 			(Event::TokensUnreserved { who: 1, amount: 50, updated_balance: 70 }).into(),
@@ -160,7 +175,7 @@ fn try_to_unreserve_tokens_being_a_not_registered_voter() {
 		let reserved_tokens = 50;
 		assert_ok!(Voting::reserve_tokens(RuntimeOrigin::signed(1), reserved_tokens));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(Voting::get_voter_balance(&1), 45);
+		assert_eq!(Voting::get_free_voting_balance(&1), 45);
 		// Dispatch a signed extrinsic.
 		let unreserved_tokens = reserved_tokens + 1;
 		assert_noop!(
@@ -171,7 +186,7 @@ fn try_to_unreserve_tokens_being_a_not_registered_voter() {
 }
 
 #[test]
-fn try_to_unreserve_empty_amount_of_tokens(){
+fn try_to_unreserve_empty_amount_of_tokens() {
 	new_test_ext().execute_with(|| {
 		let voter: AccountIdOf<Test> = 1;
 		assert_ok!(setup_new_voter(voter, 5));
@@ -179,7 +194,7 @@ fn try_to_unreserve_empty_amount_of_tokens(){
 		let reserved_tokens = 50;
 		assert_ok!(Voting::reserve_tokens(RuntimeOrigin::signed(1), reserved_tokens));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(Voting::get_voter_balance(&1), 45);
+		assert_eq!(Voting::get_free_voting_balance(&1), 45);
 		// Dispatch a signed extrinsic.
 		let unreserved_tokens = 0;
 		assert_noop!(
@@ -198,7 +213,7 @@ fn try_to_unreserve_more_tokens_than_reserved() {
 		let reserved_tokens = 50;
 		assert_ok!(Voting::reserve_tokens(RuntimeOrigin::signed(1), reserved_tokens));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(Voting::get_voter_balance(&1), 45);
+		assert_eq!(Voting::get_free_voting_balance(&1), 45);
 		// Dispatch a signed extrinsic.
 		let unreserved_tokens = reserved_tokens + 1;
 		assert_noop!(
@@ -208,8 +223,6 @@ fn try_to_unreserve_more_tokens_than_reserved() {
 	});
 }
 
-
-
 #[test]
 fn create_new_proposal() {
 	new_test_ext().execute_with(|| {
@@ -218,7 +231,7 @@ fn create_new_proposal() {
 		// Create proposal
 		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
 		// Check proposal status (the proposal index is 1 because it's the first proposal created)
-		assert_eq!(Voting::get_proposal_status(1), ProposalStatus::NotStarted);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::NotStarted));
 		// Check event
 		System::assert_last_event(
 			(Event::NewProposalCreated {
@@ -226,7 +239,9 @@ fn create_new_proposal() {
 				text: "Let's use blockchain to create a better world!"
 					.using_encoded(blake2_256)
 					.into(),
-				end_block: Voting::get_proposal_end_block(1),
+				end_block: Voting::get_proposal_end_block(1).unwrap(),
+				created_at: 1,
+				metadata: None,
 			})
 			.into(),
 		);
@@ -246,9 +261,6 @@ fn try_to_create_new_proposal_with_not_registered_voter() {
 	})
 }
 
-
-
-
 #[test]
 fn start_proposal() {
 	new_test_ext().execute_with(|| {
@@ -261,12 +273,14 @@ fn start_proposal() {
 		// Start proposal
 		assert_ok!(start_proposal_helper(voter, 1, fee_to_start_proposal));
 		// Check proposal status
-		assert_eq!(Voting::get_proposal_status(1), ProposalStatus::InProgress);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
 		// Check voter balance
 		let final_balance = 100 - fee_to_new_voter - fee_to_start_proposal;
-		assert_eq!(Voting::get_voter_balance(&voter), final_balance);
-		// Check event
-		System::assert_last_event((Event::ProposalStarted { proposal_index: 1 }).into());
+		assert_eq!(Voting::get_free_voting_balance(&voter), final_balance);
+		// Check event, the end block is the one stored
+		let end_block = Voting::get_proposal_end_block(1).unwrap();
+		assert_eq!(end_block, 101);
+		System::assert_last_event((Event::ProposalStarted { proposal_index: 1, end_block }).into());
 	})
 }
 
@@ -344,9 +358,6 @@ fn try_to_start_proposal_with_empty_fee() {
 	})
 }
 
-
-
-
 #[test]
 fn vote_proposal_with_tokens_reserved() {
 	new_test_ext().execute_with(|| {
@@ -424,7 +435,7 @@ fn try_vote_proposal_without_tokens_reserved() {
 		assert_ok!(Voting::start_proposal(RuntimeOrigin::signed(1), 1, 10));
 		// Vote proposal
 		assert_noop!(
-			Voting::vote_proposal(RuntimeOrigin::signed(1), 1, Vote::Aye),
+			Voting::vote_proposal(RuntimeOrigin::signed(1), 1, 10, Vote::Aye, 0),
 			Error::<Test>::NotEnoughReservedTokens
 		);
 	})
@@ -511,17 +522,14 @@ fn check_proposal_winner() {
 		assert_ok!(reserve_tokens_helper(voter_2, 10));
 		assert_ok!(vote_proposal(voter_2, 2, Vote::Aye));
 		// End voting
-		assert_eq!(Voting::get_winner(), 2);
+		assert_eq!(Voting::get_winner(), Some(2));
 		// Check event
-		System::assert_last_event((Event::VotingEnded { winner: 2 }).into());
+		System::assert_last_event((Event::VotingEnded { winner: Some(2) }).into());
 	})
 }
 
-
-
-
 #[test]
-fn vote_multiples_proposals(){
+fn vote_multiples_proposals() {
 	new_test_ext().execute_with(|| {
 		let voter = 1;
 		assert_ok!(setup_new_voter(voter, 5));
@@ -540,12 +548,12 @@ fn vote_multiples_proposals(){
 			vec![(1, 50, Vote::Aye), (2, 20, Vote::Aye)];
 		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
 		// Check event
-		System::assert_last_event((Event::ProposalsVoted { proposals: [1,2].into() }).into());
+		System::assert_last_event((Event::BatchVoteOutcome { succeeded: vec![1, 2] }).into());
 	})
 }
 
 #[test]
-fn vote_multiples_proposal_when_voting_has_ended(){
+fn vote_multiples_proposal_when_voting_has_ended() {
 	new_test_ext().execute_with(|| {
 		let voter = 1;
 		assert_ok!(setup_new_voter(voter, 5));
@@ -564,22 +572,22 @@ fn vote_multiples_proposal_when_voting_has_ended(){
 		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
 			vec![(1, 10, Vote::Aye), (2, 5, Vote::Aye)];
 		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
-		// System::assert_last_event((Event::ProposalsVoted { proposals: [1,2].into() }).into());
+		// System::assert_last_event((Event::BatchVoteOutcome { succeeded: vec![1, 2] }).into());
 		// Check event
 		// System::assert_last_event((Event::winners(1, 2)).into());
 		// Go past voting period
-		let voter  = 2;
+		let voter = 2;
 		assert_ok!(setup_new_voter(voter, 5));
 		// Reserve Tokens
 		assert_ok!(reserve_tokens_helper(voter, 10));
 		System::set_block_number(250);
 		// Vote proposals at once
 		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
-		vec![(1, 5, Vote::Aye), (2, 4, Vote::Aye)];
+			vec![(1, 5, Vote::Aye), (2, 4, Vote::Aye)];
 
 		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
 		// Check events
-		System::assert_last_event((Event::VotingEnded { winner: 1 }).into());
+		System::assert_last_event((Event::VotingEnded { winner: Some(1) }).into());
 	})
 }
 
@@ -617,7 +625,7 @@ fn try_to_vote_multiple_proposals_with_proposal_not_found() {
 			vec![(1, 10, Vote::Aye), (2, 15, Vote::Aye)];
 		assert_noop!(
 			vote_multiple_proposals_helper(voter, proposals.clone()),
-			Error::<Test>::AtLeastOneProposalNotRegisteredOrNotActive
+			Error::<Test>::ProposalNotFound
 		);
 		// Create proposal
 		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
@@ -626,7 +634,7 @@ fn try_to_vote_multiple_proposals_with_proposal_not_found() {
 		// Proposal 1 is created and started but 2 doesn't exist
 		assert_noop!(
 			vote_multiple_proposals_helper(voter, proposals),
-			Error::<Test>::AtLeastOneProposalNotRegisteredOrNotActive
+			Error::<Test>::ProposalNotFound
 		);
 	})
 }
@@ -645,14 +653,14 @@ fn try_to_vote_multiple_proposals_with_proposal_not_started() {
 			vec![(1, 10, Vote::Aye), (2, 15, Vote::Aye)];
 		assert_noop!(
 			vote_multiple_proposals_helper(voter, proposals.clone()),
-			Error::<Test>::AtLeastOneProposalNotRegisteredOrNotActive
+			Error::<Test>::ProposalNotActive
 		);
 		// Start proposal
 		assert_ok!(start_proposal_helper(voter, 1, 10));
 		// But not the other one
 		assert_noop!(
 			vote_multiple_proposals_helper(voter, proposals.clone()),
-			Error::<Test>::AtLeastOneProposalNotRegisteredOrNotActive
+			Error::<Test>::ProposalNotActive
 		);
 	})
 }
@@ -681,7 +689,7 @@ fn try_to_vote_multiples_proposals_without_enough_token_reserved() {
 }
 
 #[test]
-fn try_to_vote_multiple_proposals_when_the_voter_already_had_voted_one(){
+fn try_to_vote_multiple_proposals_when_the_voter_already_had_voted_one() {
 	new_test_ext().execute_with(|| {
 		let voter = 1;
 		assert_ok!(setup_new_voter(voter, 5));
@@ -696,7 +704,7 @@ fn try_to_vote_multiple_proposals_when_the_voter_already_had_voted_one(){
 		// Reserve some tokens
 		assert_ok!(reserve_tokens_helper(voter, 75));
 		// Vote proposal 1
-		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 50, Vote::Aye));
 		// Vote proposals at once
 		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
 			vec![(1, 10, Vote::Aye), (2, 15, Vote::Aye)];
@@ -707,50 +715,3924 @@ fn try_to_vote_multiple_proposals_when_the_voter_already_had_voted_one(){
 	})
 }
 
+#[test]
+fn vote_proposal_with_tokens_reserved_before_start_when_pre_reserve_is_required() {
+	new_test_ext().execute_with(|| {
+		RequirePreReserve::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		// Reserve tokens before the proposal starts
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		System::set_block_number(5);
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		// Vote proposal
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+	})
+}
 
+#[test]
+fn try_to_vote_proposal_with_tokens_reserved_after_start_when_pre_reserve_is_required() {
+	new_test_ext().execute_with(|| {
+		RequirePreReserve::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		// Reserve tokens once the proposal is already running
+		System::set_block_number(5);
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_noop!(vote_proposal(voter, 1, Vote::Aye), Error::<Test>::ReservedTooLate);
+		// Without the requirement the same vote is accepted
+		RequirePreReserve::set(&false);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+	})
+}
 
+#[test]
+fn register_voter_twice_when_registration_is_idempotent() {
+	new_test_ext().execute_with(|| {
+		RegistrationIdempotent::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		// Registering again is accepted but doesn't touch the balance
+		assert_ok!(setup_new_voter(voter, 10));
+		assert_eq!(Voting::is_voter_registered(&voter), true);
+		assert_eq!(Voting::get_free_voting_balance(&voter), 45);
+	});
+}
 
-// Helper Functions
-fn setup_new_voter(
-	voter: AccountIdOf<Test>,
-	fee: BalanceOf<Test>,
-) -> Result<(), sp_runtime::DispatchError> {
-	System::set_block_number(1);
-	Voting::register_voter(RawOrigin::Root.into(),voter, fee)
+#[test]
+fn try_register_voter_twice_when_registration_is_not_idempotent() {
+	new_test_ext().execute_with(|| {
+		RegistrationIdempotent::set(&false);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_noop!(setup_new_voter(voter, 5), Error::<Test>::VoterAlreadyRegistered);
+	});
 }
-fn reserve_tokens_helper(
-	voter: AccountIdOf<Test>,
-	amount: BalanceOf<Test>,
-) -> Result<(), sp_runtime::DispatchError> {
-	Voting::reserve_tokens(RuntimeOrigin::signed(voter), amount)
+
+#[test]
+fn register_voter_emits_balance_flow_events() {
+	new_test_ext().execute_with(|| {
+		let voter_id = 1;
+		let fee = 5;
+		assert_ok!(setup_new_voter(voter_id, fee));
+		let voting_events: Vec<RuntimeEvent> = System::events()
+			.into_iter()
+			.map(|record| record.event)
+			.filter(|event| matches!(event, RuntimeEvent::Voting(_)))
+			.collect();
+		let expected_events: Vec<RuntimeEvent> = vec![
+			Event::BalanceInitialized { who: voter_id, amount: 100 - fee }.into(),
+			Event::RegistrationFeeCollected { who: voter_id, amount: fee }.into(),
+			Event::VoterRegistered { voter_id, initial_balance: 100 - fee }.into(),
+		];
+		assert_eq!(voting_events, expected_events);
+	});
 }
-fn unreserve_tokens_helper(
-	voter: AccountIdOf<Test>,
-	amount: BalanceOf<Test>,
-) -> Result<(), sp_runtime::DispatchError> {
-	Voting::unreserve_tokens(RuntimeOrigin::signed(voter), amount)
+
+#[test]
+fn vote_proposal_after_creating_the_minimum_number_of_proposals() {
+	new_test_ext().execute_with(|| {
+		MinProposalsToVote::set(&2);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		// Only one proposal created, one below the requirement
+		assert_noop!(vote_proposal(voter, 1, Vote::Aye), Error::<Test>::MustProposeFirst);
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> = vec![(1, 10, Vote::Aye)];
+		assert_noop!(
+			vote_multiple_proposals_helper(voter, proposals),
+			Error::<Test>::MustProposeFirst
+		);
+		// Reaching the requirement unlocks voting
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+	})
 }
-fn create_proposal(voter: AccountIdOf<Test>, text: &str) -> Result<(), sp_runtime::DispatchError> {
-	let hashed_text: H256 = text.using_encoded(blake2_256).into();
-	Voting::create_proposal(RuntimeOrigin::signed(voter), hashed_text)
+
+#[test]
+fn winner_outcome_without_votes() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(Voting::get_winner_outcome(), WinnerOutcome::NoVotes);
+	})
 }
-fn start_proposal_helper(
-	voter: AccountIdOf<Test>,
-	proposal_index: ProposalIndex,
-	fee: BalanceOf<Test>,
-) -> Result<(), sp_runtime::DispatchError> {
-	Voting::start_proposal(RuntimeOrigin::signed(voter), proposal_index, fee)
+
+#[test]
+fn winner_outcome_with_leader_below_and_above_threshold() {
+	new_test_ext().execute_with(|| {
+		PassThreshold::set(&10);
+		let voter_1 = 1;
+		let voter_2 = 2;
+		assert_ok!(setup_new_voter(voter_1, 5));
+		assert_ok!(setup_new_voter(voter_2, 5));
+		assert_ok!(create_proposal(voter_1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter_1, 1, 10));
+		// sqrt(49) = 7 votes, below the threshold
+		assert_ok!(reserve_tokens_helper(voter_1, 49));
+		assert_ok!(vote_proposal(voter_1, 1, Vote::Aye));
+		assert_eq!(Voting::get_winner_outcome(), WinnerOutcome::NoPass(1, 7));
+		// sqrt(36) = 6 more votes, 13 in total
+		assert_ok!(reserve_tokens_helper(voter_2, 36));
+		assert_ok!(vote_proposal(voter_2, 1, Vote::Aye));
+		assert_eq!(Voting::get_winner_outcome(), WinnerOutcome::Winner(1, 13));
+	})
 }
-fn vote_proposal(
+
+#[test]
+fn invalidate_result_within_dispute_window() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		// Voting after the end block completes the proposal
+		let end_block = Voting::get_proposal_end_block(1).unwrap();
+		System::set_block_number(end_block);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		// Invalidate the result inside the dispute window
+		System::set_block_number(end_block + 5);
+		assert_ok!(Voting::invalidate_result(RawOrigin::Root.into(), 1));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+		assert_eq!(Voting::get_proposal_end_block(1), Ok(end_block + 5 + 100));
+		System::assert_last_event(
+			(Event::ResultInvalidated { proposal_index: 1, end_block: end_block + 105 }).into(),
+		);
+		// The reopened proposal accepts votes again
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+	})
+}
+
+#[test]
+fn try_to_invalidate_result_after_dispute_window() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		// A proposal that didn't complete can't be invalidated
+		assert_noop!(
+			Voting::invalidate_result(RawOrigin::Root.into(), 1),
+			Error::<Test>::ProposalNotCompleted
+		);
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		let end_block = Voting::get_proposal_end_block(1).unwrap();
+		System::set_block_number(end_block);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		// Go past the dispute window
+		System::set_block_number(end_block + 11);
+		assert_noop!(
+			Voting::invalidate_result(RawOrigin::Root.into(), 1),
+			Error::<Test>::DisputeWindowClosed
+		);
+	})
+}
+
+#[test]
+fn reserve_yield_accrues_every_yield_period() {
+	new_test_ext().execute_with(|| {
+		ReserveYield::set(&Percent::from_percent(10));
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		// No yield outside of a yield round
+		Voting::on_initialize(9);
+		assert_eq!(Balances::reserved_balance(&voter), 50);
+		// The yield period is 10 blocks in the mock
+		Voting::on_initialize(10);
+		assert_eq!(Balances::reserved_balance(&voter), 55);
+		assert_eq!(Voting::get_free_voting_balance(&voter), 45);
+		System::assert_last_event((Event::YieldAccrued { who: voter, amount: 5 }).into());
+		// The round is over until the next period
+		Voting::on_initialize(11);
+		assert_eq!(Balances::reserved_balance(&voter), 55);
+	})
+}
+
+#[test]
+fn reserve_yield_round_is_paged_across_blocks() {
+	new_test_ext().execute_with(|| {
+		ReserveYield::set(&Percent::from_percent(10));
+		MaxYieldPayoutsPerBlock::set(&1);
+		assert_ok!(setup_new_voter(1, 5));
+		assert_ok!(setup_new_voter(2, 5));
+		assert_ok!(reserve_tokens_helper(1, 50));
+		assert_ok!(reserve_tokens_helper(2, 50));
+		// Only one voter is credited per block
+		Voting::on_initialize(10);
+		assert_eq!(Balances::reserved_balance(&1) + Balances::reserved_balance(&2), 105);
+		Voting::on_initialize(11);
+		assert_eq!(Balances::reserved_balance(&1), 55);
+		assert_eq!(Balances::reserved_balance(&2), 55);
+	})
+}
+
+#[test]
+fn try_state_detects_proposals_counter_desync() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(Voting::do_try_state());
+		// Remove an entry behind the counter's back
+		frame_support::storage::unhashed::kill(&Proposals::<Test>::hashed_key_for(1));
+		assert_eq!(Proposals::<Test>::count(), 2);
+		assert!(Voting::do_try_state().is_err());
+	})
+}
+
+#[test]
+fn long_standing_voters_weigh_more_when_age_weighting_is_enabled() {
+	new_test_ext().execute_with(|| {
+		AgeWeighting::set(&true);
+		let old_voter = 1;
+		let new_voter = 2;
+		// Registered at block 1
+		assert_ok!(setup_new_voter(old_voter, 5));
+		// Registered 249 blocks later, two full voting periods after the first one
+		System::set_block_number(250);
+		assert_ok!(Voting::register_voter(RawOrigin::Root.into(), new_voter, 5, false));
+		assert_ok!(create_proposal(old_voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(old_voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(old_voter, 49));
+		assert_ok!(reserve_tokens_helper(new_voter, 49));
+		assert_ok!(vote_proposal(old_voter, 1, Vote::Aye));
+		assert_ok!(vote_proposal(new_voter, 1, Vote::Aye));
+		// sqrt(49) = 7 votes, multiplied by 1 + two full voting periods
+		assert_eq!(AyeVotes::<Test>::get(1, old_voter), 21);
+		assert_eq!(AyeVotes::<Test>::get(1, new_voter), 7);
+	})
+}
+
+#[test]
+fn age_multiplier_is_capped() {
+	new_test_ext().execute_with(|| {
+		AgeWeighting::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		System::set_block_number(10_000);
+		assert_eq!(Voting::age_multiplier(&voter), 4);
+	})
+}
+
+#[test]
+fn start_fee_is_refunded_on_completion() {
+	new_test_ext().execute_with(|| {
+		RefundStartFee::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_eq!(Voting::get_free_voting_balance(&voter), 35);
+		// Complete the proposal
+		System::set_block_number(Voting::get_proposal_end_block(1).unwrap());
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		System::assert_has_event(
+			(Event::StartFeeRefunded { proposal_index: 1, amount: 10 }).into(),
+		);
+		assert_eq!(Voting::get_free_voting_balance(&voter), 45);
+	})
+}
+
+#[test]
+fn start_fee_is_kept_on_completion_without_refunds() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		System::set_block_number(Voting::get_proposal_end_block(1).unwrap());
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(Voting::get_free_voting_balance(&voter), 35);
+	})
+}
+
+#[test]
+fn reserve_tokens_for_another_voter() {
+	new_test_ext().execute_with(|| {
+		let sponsor = 1;
+		let beneficiary = 2;
+		assert_ok!(setup_new_voter(sponsor, 5));
+		assert_ok!(setup_new_voter(beneficiary, 5));
+		assert_ok!(create_proposal(sponsor, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(sponsor, 1, 10));
+		// The sponsor pays for the reservation
+		assert_ok!(Voting::reserve_for(RuntimeOrigin::signed(sponsor), beneficiary, 49));
+		System::assert_last_event(
+			(Event::TokensReservedFor { sponsor, beneficiary, amount: 49 }).into(),
+		);
+		assert_eq!(Voting::get_free_voting_balance(&sponsor), 36);
+		assert_eq!(Voting::get_free_voting_balance(&beneficiary), 95);
+		assert_eq!(Voting::get_votable_reserve(&sponsor), 0);
+		assert_eq!(Voting::get_votable_reserve(&beneficiary), 49);
+		// The beneficiary votes with the sponsored tokens
+		assert_ok!(vote_proposal(beneficiary, 1, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(1, beneficiary), 7);
+		// But the sponsor can't vote or unreserve them
+		assert_noop!(vote_proposal(sponsor, 1, Vote::Aye), Error::<Test>::NotEnoughReservedTokens);
+		assert_noop!(unreserve_tokens_helper(sponsor, 49), Error::<Test>::NotEnoughReservedTokens);
+	})
+}
+
+#[test]
+fn try_to_reserve_tokens_for_a_not_registered_voter() {
+	new_test_ext().execute_with(|| {
+		let sponsor = 1;
+		assert_ok!(setup_new_voter(sponsor, 5));
+		assert_noop!(
+			Voting::reserve_for(RuntimeOrigin::signed(sponsor), 2, 49),
+			Error::<Test>::NotRegisteredVoter
+		);
+	})
+}
+
+#[test]
+fn proposal_timeline_follows_the_lifecycle() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_eq!(Voting::proposal_timeline(1), None);
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_eq!(
+			Voting::proposal_timeline(1),
+			Some(ProposalTimeline {
+				created_at: 1,
+				started_at: None,
+				end_block: 101,
+				completed_at: None,
+			})
+		);
+		System::set_block_number(5);
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_eq!(Voting::proposal_timeline(1).unwrap().started_at, Some(5));
+		// Complete the proposal
+		System::set_block_number(105);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(
+			Voting::proposal_timeline(1),
+			Some(ProposalTimeline {
+				created_at: 1,
+				started_at: Some(5),
+				end_block: 105,
+				completed_at: Some(105),
+			})
+		);
+	})
+}
+
+#[test]
+fn migrate_proposals_to_v1() {
+	new_test_ext().execute_with(|| {
+		let text: H256 = "Let's use blockchain to create a better world!"
+			.using_encoded(blake2_256)
+			.into();
+		// Proposal stored with the layout previous to v1
+		let old_proposal = (1u32, text, 1u64, 101u64, ProposalStatus::Completed);
+		frame_support::storage::unhashed::put(&Proposals::<Test>::hashed_key_for(1), &old_proposal);
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(0));
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(1));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(
+			Voting::proposal_timeline(1),
+			Some(ProposalTimeline {
+				created_at: 1,
+				started_at: Some(1),
+				end_block: 101,
+				completed_at: Some(101),
+			})
+		);
+	})
+}
+
+#[test]
+fn late_votes_weigh_less_with_weight_decay() {
+	new_test_ext().execute_with(|| {
+		WeightDecay::set(&Percent::from_percent(50));
+		let early_voter = 1;
+		let late_voter = 2;
+		assert_ok!(setup_new_voter(early_voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		// Voting window goes from block 1 to block 101
+		assert_ok!(create_proposal(early_voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(early_voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(early_voter, 49));
+		assert_ok!(reserve_tokens_helper(late_voter, 49));
+		assert_ok!(vote_proposal(early_voter, 1, Vote::Aye));
+		// 90% of the window elapsed: 7 - 50% * 7 * 90 / 100 = 4
+		System::set_block_number(91);
+		assert_ok!(vote_proposal(late_voter, 1, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(1, early_voter), 7);
+		assert_eq!(AyeVotes::<Test>::get(1, late_voter), 4);
+	})
+}
+
+#[test]
+fn reserve_discrepancy_equals_uncommitted_reserve() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 70));
+		// Nothing committed yet
+		assert_eq!(Voting::reserve_discrepancy(&voter), 70);
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
+			vec![(1, 50, Vote::Aye), (2, 10, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		assert_eq!(Voting::get_committed_tokens(&voter), 60);
+		assert_eq!(Voting::reserve_discrepancy(&voter), 10);
+
+		// Reserve delegated by other voters isn't part of the voter's own reserved balance
+		let delegator = 2;
+		assert_ok!(setup_new_voter(delegator, 5));
+		assert_ok!(reserve_tokens_helper(delegator, 30));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(delegator), voter));
+		assert_eq!(Voting::get_available_reserve(&voter), 40);
+		assert_eq!(Voting::reserve_discrepancy(&voter), 10);
+	})
+}
+
+#[test]
+fn reserve_discrepancy_surfaces_accounting_drift() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 70));
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
+			vec![(1, 50, Vote::Aye), (2, 10, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		// Simulate a bug releasing committed reserve behind the pallet's back
+		Balances::unreserve(&voter, 30);
+		assert_eq!(Voting::get_votable_reserve(&voter), 40);
+		assert_eq!(Voting::get_committed_tokens(&voter), 60);
+		assert_eq!(Voting::reserve_discrepancy(&voter), 0);
+	})
+}
+
+#[test]
+fn recompute_pending_end_blocks_after_changing_the_voting_period() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_eq!(Voting::get_proposal_end_block(2), Ok(101));
+		// Shorten the voting period
+		VotingPeriod::set(&50);
+		assert_noop!(
+			Voting::recompute_pending_end_blocks(RuntimeOrigin::signed(voter)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Voting::recompute_pending_end_blocks(RawOrigin::Root.into()));
+		System::assert_last_event((Event::EndBlocksRecomputed { count: 1 }).into());
+		// Only the pending proposal is updated
+		assert_eq!(Voting::get_proposal_end_block(1), Ok(101));
+		assert_eq!(Voting::get_proposal_end_block(2), Ok(51));
+	})
+}
+
+#[test]
+fn higher_conviction_yields_more_weight() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let convinced_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(convinced_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		// sqrt(49) = 7, multiplied by (1 + periods)
+		assert_ok!(Voting::vote_with_conviction(RuntimeOrigin::signed(voter), 1, 49, 0, Vote::Aye));
+		assert_ok!(Voting::vote_with_conviction(
+			RuntimeOrigin::signed(convinced_voter),
+			1,
+			49,
+			2,
+			Vote::Aye
+		));
+		assert_eq!(AyeVotes::<Test>::get(1, voter), 7);
+		assert_eq!(AyeVotes::<Test>::get(1, convinced_voter), 21);
+		// Too many lock periods
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_noop!(
+			Voting::vote_with_conviction(RuntimeOrigin::signed(voter), 2, 49, 7, Vote::Aye),
+			Error::<Test>::ConvictionTooHigh
+		);
+	})
+}
+
+#[test]
+fn conviction_locks_tokens_for_the_committed_periods() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let convinced_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(convinced_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		// The lock period is 10 blocks in the mock
+		assert_ok!(Voting::vote_with_conviction(RuntimeOrigin::signed(voter), 1, 49, 1, Vote::Aye));
+		assert_ok!(Voting::vote_with_conviction(
+			RuntimeOrigin::signed(convinced_voter),
+			1,
+			49,
+			3,
+			Vote::Aye
+		));
+		System::assert_has_event(
+			(Event::TokensLocked { who: convinced_voter, amount: 49, until: 31 }).into(),
+		);
+		assert_eq!(ConvictionLocks::<Test>::get(voter), Some((49, 11)));
+		assert_eq!(ConvictionLocks::<Test>::get(convinced_voter), Some((49, 31)));
+		assert_eq!(Balances::usable_balance(&convinced_voter), 95 - 49);
+
+		System::set_block_number(11);
+		assert_ok!(Voting::unlock_tokens(RuntimeOrigin::signed(voter)));
+		assert_noop!(
+			Voting::unlock_tokens(RuntimeOrigin::signed(convinced_voter)),
+			Error::<Test>::LockNotExpired
+		);
+		System::set_block_number(31);
+		assert_ok!(Voting::unlock_tokens(RuntimeOrigin::signed(convinced_voter)));
+		System::assert_last_event(
+			(Event::TokensUnlocked { who: convinced_voter, amount: 49 }).into(),
+		);
+		assert_eq!(Balances::usable_balance(&convinced_voter), 95);
+		assert_noop!(
+			Voting::unlock_tokens(RuntimeOrigin::signed(convinced_voter)),
+			Error::<Test>::NoConvictionLock
+		);
+	})
+}
+
+#[test]
+fn emit_winner_reports_the_current_leader() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(Voting::emit_winner(RuntimeOrigin::signed(voter)));
+		System::assert_last_event((Event::CurrentWinner { proposal_index: 0, score: 0 }).into());
+		assert_ok!(reserve_tokens_helper(voter, 70));
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
+			vec![(1, 16, Vote::Aye), (2, 49, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		assert_ok!(Voting::emit_winner(RuntimeOrigin::signed(voter)));
+		System::assert_last_event((Event::CurrentWinner { proposal_index: 2, score: 7 }).into());
+		// Proposals are still in progress
+		assert!(Voting::is_proposal_active(1));
+		assert!(Voting::is_proposal_active(2));
+	})
+}
+
+#[test]
+fn repeated_votes_are_discounted() {
+	new_test_ext().execute_with(|| {
+		AllowRepeatVotes::set(&true);
+		RepeatVoteDiscount::set(&Percent::from_percent(50));
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 72));
+		// The first vote counts full
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 36, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(1, voter), 6);
+		// The second one counts 6 - 50% * 6 = 3
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 36, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(1, voter), 9);
+		assert_eq!(VoteCounts::<Test>::get(1, voter), 2);
+	})
+}
+
+#[test]
+fn repeated_votes_are_rejected_without_the_flag() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_noop!(vote_proposal(voter, 1, Vote::Aye), Error::<Test>::VoterAlreadyVoted);
+	})
+}
+
+#[test]
+fn archive_and_restore_a_draft() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_noop!(
+			Voting::archive_draft(RuntimeOrigin::signed(other_voter), 1),
+			Error::<Test>::NotProposer
+		);
+		assert_ok!(Voting::archive_draft(RuntimeOrigin::signed(voter), 1));
+		System::assert_last_event((Event::ProposalArchived { proposal_index: 1 }).into());
+		assert_eq!(Proposals::<Test>::count(), 0);
+		assert_eq!(Drafts::<Test>::count(), 1);
+		assert!(!Voting::is_proposal_registered(1));
+		// New proposals don't reuse the archived index
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert!(Voting::is_proposal_registered(2));
+
+		assert_noop!(
+			Voting::restore_draft(RuntimeOrigin::signed(other_voter), 1),
+			Error::<Test>::NotProposer
+		);
+		assert_ok!(Voting::restore_draft(RuntimeOrigin::signed(voter), 1));
+		System::assert_last_event((Event::ProposalRestored { proposal_index: 1 }).into());
+		assert_eq!(Proposals::<Test>::count(), 2);
+		assert_eq!(Drafts::<Test>::count(), 0);
+		assert_eq!(Voting::get_proposal(1).unwrap().status, ProposalStatus::NotStarted);
+	})
+}
+
+#[test]
+fn try_to_archive_a_started_proposal() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_noop!(
+			Voting::archive_draft(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::ProposalAlreadyStarted
+		);
+		assert_noop!(
+			Voting::restore_draft(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::DraftNotFound
+		);
+	})
+}
+
+#[test]
+fn influence_concentration_of_a_skewed_proposal_is_high() {
+	new_test_ext().execute_with(|| {
+		let whale = 1;
+		let voter = 2;
+		assert_ok!(setup_new_voter(whale, 5));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(whale, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(whale, 1, 10));
+		assert_eq!(Voting::influence_concentration(1), Percent::from_percent(0));
+		assert_ok!(reserve_tokens_helper(whale, 81));
+		assert_ok!(reserve_tokens_helper(voter, 1));
+		assert_ok!(vote_proposal(whale, 1, Vote::Aye));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		// Weights are 9 and 1
+		assert_eq!(Voting::influence_concentration(1), Percent::from_percent(90));
+	})
+}
+
+#[test]
+fn influence_concentration_of_an_even_proposal_is_low() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(reserve_tokens_helper(other_voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Aye));
+		assert_eq!(Voting::influence_concentration(1), Percent::from_percent(50));
+	})
+}
+
+#[test]
+fn committed_tokens_are_unreserved_on_completion() {
+	new_test_ext().execute_with(|| {
+		AutoUnreserveOnCompletion::set(&true);
+		let voter = 1;
+		let late_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(reserve_tokens_helper(late_voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_free_voting_balance(&voter), 36);
+		// Voting after the end block completes the proposal
+		System::set_block_number(102);
+		assert_ok!(vote_proposal(late_voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_proposal(1).unwrap().status, ProposalStatus::Completed);
+		System::assert_has_event(
+			(Event::VotesRefunded { proposal_index: 1, who: voter, amount: 49 }).into(),
+		);
+		// No penalty applied
+		assert_eq!(Balances::reserved_balance(&voter), 0);
+		assert_eq!(Voting::get_free_voting_balance(&voter), 85);
+		// The late voter never committed anything
+		assert_eq!(Balances::reserved_balance(&late_voter), 49);
+	})
+}
+
+#[test]
+fn try_to_vote_past_the_proposal_reserve_cap() {
+	new_test_ext().execute_with(|| {
+		MaxProposalReserve::set(&60);
+		let voter = 1;
+		let other_voter = 2;
+		let late_voter = 3;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 40));
+		assert_ok!(reserve_tokens_helper(other_voter, 20));
+		assert_ok!(reserve_tokens_helper(late_voter, 1));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Aye));
+		// The proposal is filled up to the cap
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 60);
+		assert_noop!(
+			vote_proposal(late_voter, 1, Vote::Aye),
+			Error::<Test>::ProposalReserveCapReached
+		);
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> = vec![(1, 1, Vote::Aye)];
+		assert_noop!(
+			vote_multiple_proposals_helper(late_voter, proposals),
+			Error::<Test>::ProposalReserveCapReached
+		);
+	})
+}
+
+#[test]
+fn proposals_voted_by_returns_every_voted_proposal() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(create_proposal(voter, "Quadratic voting is fair!"));
+		assert_ok!(start_proposal_helper(voter, 3, 10));
+		assert_eq!(Voting::proposals_voted_by(&voter), Vec::<ProposalIndex>::new());
+		assert_ok!(reserve_tokens_helper(voter, 60));
+		assert_ok!(vote_proposal_with_tokens(voter, 3, 40, Vote::Aye));
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
+			vec![(1, 10, Vote::Aye), (2, 10, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		assert_eq!(Voting::proposals_voted_by(&voter), vec![3, 1, 2]);
+	})
+}
+
+#[test]
+fn voting_ends_with_a_winner_outpacing_the_runner_up() {
+	new_test_ext().execute_with(|| {
+		WinMargin::set(&Percent::from_percent(50));
+		let voter = 1;
+		let late_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 65));
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
+			vec![(1, 49, Vote::Aye), (2, 16, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		assert_eq!(Voting::get_runner_up(), Some((2, 4)));
+		// 7 >= 4 + 50% * 4
+		System::set_block_number(102);
+		assert_ok!(vote_proposal(late_voter, 1, Vote::Aye));
+		System::assert_last_event((Event::VotingEnded { winner: Some(1) }).into());
+	})
+}
+
+#[test]
+fn voting_ends_inconclusive_within_the_margin() {
+	new_test_ext().execute_with(|| {
+		WinMargin::set(&Percent::from_percent(50));
+		let voter = 1;
+		let other_voter = 2;
+		let late_voter = 3;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(reserve_tokens_helper(other_voter, 36));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_ok!(vote_proposal(other_voter, 2, Vote::Aye));
+		// 7 < 6 + 50% * 6
+		System::set_block_number(102);
+		assert_ok!(vote_proposal(late_voter, 1, Vote::Aye));
+		System::assert_last_event((Event::InconclusiveResult { leader: 1, runner_up: 2 }).into());
+	})
+}
+
+#[test]
+fn voting_paths_log_without_panicking() {
+	sp_tracing::try_init_simple();
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 65));
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 49, Vote::Aye));
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> = vec![(2, 16, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		assert_eq!(Voting::get_winner(), Some(1));
+	})
+}
+
+#[test]
+fn try_to_vote_past_the_proposal_voter_cap() {
+	new_test_ext().execute_with(|| {
+		MaxVotersPerProposal::set(&2);
+		AllowRepeatVotes::set(&true);
+		let voter = 1;
+		let other_voter = 2;
+		let new_voter = 3;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(setup_new_voter(new_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 60));
+		assert_ok!(reserve_tokens_helper(other_voter, 49));
+		assert_ok!(reserve_tokens_helper(new_voter, 49));
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 49, Vote::Aye));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Aye));
+		assert_eq!(ProposalVoterCount::<Test>::get(1), 2);
+		assert_noop!(
+			vote_proposal(new_voter, 1, Vote::Aye),
+			Error::<Test>::ProposalVoterCapReached
+		);
+		// Existing voters can still vote
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(ProposalVoterCount::<Test>::get(1), 2);
+	})
+}
+
+#[test]
+fn bulk_voting_status_reports_every_account() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let idle_voter = 2;
+		let unregistered = 3;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(idle_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(
+			Voting::bulk_voting_status(vec![voter, idle_voter, unregistered], 1),
+			vec![(voter, true, true), (idle_voter, true, false), (unregistered, false, false)]
+		);
+		// The query is bounded to 3 accounts in the mock
+		assert_eq!(Voting::bulk_voting_status(vec![voter, voter, voter, idle_voter], 1).len(), 3);
+	})
+}
+
+#[test]
+fn unstarted_proposals_expire_after_the_start_deadline() {
+	new_test_ext().execute_with(|| {
+		ProposalStartDeadline::set(&20);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		// Still within the deadline
+		Voting::on_idle(20, Weight::MAX);
+		assert!(Voting::is_proposal_registered(1));
+		System::set_block_number(21);
+		Voting::on_idle(21, Weight::MAX);
+		System::assert_last_event((Event::ProposalExpiredUnstarted { proposal_index: 1 }).into());
+		assert!(!Voting::is_proposal_registered(1));
+		// The started proposal stays
+		assert!(Voting::is_proposal_registered(2));
+		// Like a cancelled proposal, it no longer counts for the proposer
+		assert_eq!(ProposalsByProposer::<Test>::get(voter), 1);
+		assert_eq!(ProposerIndex::<Test>::get(voter).into_inner(), vec![2]);
+		// No bond is taken at creation, so the balance only lacks the fees paid
+		assert_eq!(Voting::get_free_voting_balance(&voter), 85);
+		// Indexes aren't reused
+		assert_ok!(create_proposal(voter, "Quadratic voting is fair!"));
+		assert!(Voting::is_proposal_registered(3));
+	})
+}
+
+#[test]
+fn try_to_create_a_proposal_with_empty_text() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		let empty_text: H256 = "".using_encoded(blake2_256).into();
+		assert_eq!(empty_text.as_bytes(), &EMPTY_TEXT_HASH[..]);
+		// Accepted when the check is disabled
+		assert_ok!(create_proposal(voter, ""));
+		RejectEmptyText::set(&true);
+		assert_noop!(create_proposal(voter, ""), Error::<Test>::EmptyProposalText);
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+	})
+}
+
+#[test]
+fn election_result_bundles_the_whole_election() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		let late_voter = 3;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 65));
+		assert_ok!(reserve_tokens_helper(other_voter, 9));
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
+			vec![(1, 49, Vote::Aye), (2, 16, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Aye));
+		// Voting after the end block completes the proposal
+		System::set_block_number(102);
+		assert_ok!(vote_proposal(late_voter, 1, Vote::Aye));
+		assert_eq!(
+			Voting::election_result(),
+			ElectionResult {
+				winner: Some((1, 10)),
+				runner_up: Some((2, 4)),
+				tallies: vec![(1, 10), (2, 4)],
+				turnout: 3,
+				finalized_at: Some(102),
+			}
+		);
+	})
+}
+
+#[test]
+fn fund_reward_pool() {
+	new_test_ext().execute_with(|| {
+		let funder = 1;
+		assert_ok!(setup_new_voter(funder, 5));
+		assert_eq!(Voting::reward_pool_balance(), 0);
+		assert_ok!(Voting::fund_reward_pool(RuntimeOrigin::signed(funder), 20));
+		System::assert_last_event((Event::RewardPoolFunded { who: funder, amount: 20 }).into());
+		assert_eq!(Voting::reward_pool_balance(), 20);
+		assert_eq!(Voting::get_free_voting_balance(&funder), 75);
+	})
+}
+
+#[test]
+fn winner_is_rewarded_from_the_pool() {
+	new_test_ext().execute_with(|| {
+		PoolReward::set(&15);
+		let voter = 1;
+		let late_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		assert_ok!(Voting::fund_reward_pool(RuntimeOrigin::signed(late_voter), 20));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		// Voting after the end block completes the proposal
+		System::set_block_number(102);
+		assert_ok!(vote_proposal(late_voter, 1, Vote::Aye));
+		System::assert_has_event(
+			(Event::WinnerRewarded { proposal_index: 1, who: voter, amount: 15 }).into(),
+		);
+		assert_eq!(Voting::get_free_voting_balance(&voter), 36 + 15);
+		assert_eq!(Voting::reward_pool_balance(), 5);
+	})
+}
+
+#[test]
+fn winner_reward_is_skipped_when_the_pool_is_underfunded() {
+	new_test_ext().execute_with(|| {
+		PoolReward::set(&15);
+		let voter = 1;
+		let late_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(late_voter, 5));
+		assert_ok!(Voting::fund_reward_pool(RuntimeOrigin::signed(late_voter), 10));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		System::set_block_number(102);
+		assert_ok!(vote_proposal(late_voter, 1, Vote::Aye));
+		System::assert_has_event((Event::RewardPoolEmpty { proposal_index: 1 }).into());
+		assert_eq!(Voting::get_free_voting_balance(&voter), 36);
+		assert_eq!(Voting::reward_pool_balance(), 10);
+	})
+}
+
+#[test]
+fn vote_nay_proposal_with_tokens_reserved() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		// Create proposal
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		// Start proposal
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		// Reserve Tokens
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		// Vote proposal
+		assert_ok!(vote_proposal(voter, 1, Vote::Nay));
+		// Check event
+		System::assert_last_event(
+			(Event::ProposalVoted { proposal_index: 1, vote: Vote::Nay }).into(),
+		);
+		assert_eq!(NayVotes::<Test>::get(1, voter), 7);
+		assert!(!AyeVotes::<Test>::contains_key(1, voter));
+	})
+}
+
+#[test]
+fn try_to_vote_both_sides_of_a_proposal() {
+	new_test_ext().execute_with(|| {
+		AllowRepeatVotes::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(vote_proposal(voter, 1, Vote::Nay));
+		assert!(Voting::voter_has_voted(1, &voter));
+		assert_noop!(vote_proposal(voter, 1, Vote::Aye), Error::<Test>::VoterAlreadyVoted);
+	})
+}
+
+#[test]
+fn get_winner_uses_net_votes() {
+	new_test_ext().execute_with(|| {
+		let voter_1 = 1;
+		let voter_2 = 2;
+		let voter_3 = 3;
+		let voter_4 = 4;
+		assert_ok!(setup_new_voter(voter_1, 5));
+		assert_ok!(setup_new_voter(voter_2, 5));
+		assert_ok!(setup_new_voter(voter_3, 5));
+		assert_ok!(setup_new_voter(voter_4, 5));
+		assert_ok!(create_proposal(voter_1, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter_2, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter_1, 1, 10));
+		assert_ok!(start_proposal_helper(voter_2, 2, 10));
+
+		// Proposal 1: 7 ayes
+		assert_ok!(reserve_tokens_helper(voter_1, 49));
+		assert_ok!(vote_proposal(voter_1, 1, Vote::Aye));
+		// Proposal 2: 8 ayes, 6 nays
+		assert_ok!(reserve_tokens_helper(voter_2, 64));
+		assert_ok!(vote_proposal(voter_2, 2, Vote::Aye));
+		assert_ok!(reserve_tokens_helper(voter_3, 36));
+		assert_ok!(vote_proposal(voter_3, 2, Vote::Nay));
+
+		assert_eq!(Voting::get_net_votes(1), 7);
+		assert_eq!(Voting::get_net_votes(2), 2);
+		assert_eq!(Voting::get_winner(), Some(1));
+
+		// A net score of zero never wins
+		assert_ok!(reserve_tokens_helper(voter_4, 49));
+		assert_ok!(vote_proposal(voter_4, 1, Vote::Nay));
+		assert_eq!(Voting::get_net_votes(1), 0);
+		assert_eq!(Voting::get_winner(), Some(2));
+	})
+}
+
+#[test]
+fn abstain_vote_is_recorded() {
+	new_test_ext().execute_with(|| {
+		AllowRepeatVotes::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(vote_proposal(voter, 1, Vote::Abstain));
+		System::assert_last_event(
+			(Event::ProposalVoted { proposal_index: 1, vote: Vote::Abstain }).into(),
+		);
+		assert!(AbstainVotes::<Test>::contains_key(1, voter));
+		assert!(Voting::voter_has_voted(1, &voter));
+		assert_eq!(ProposalVoterCount::<Test>::get(1), 1);
+		// Abstainers can't switch sides later on
+		assert_noop!(vote_proposal(voter, 1, Vote::Aye), Error::<Test>::VoterAlreadyVoted);
+		assert_noop!(vote_proposal(voter, 1, Vote::Abstain), Error::<Test>::VoterAlreadyVoted);
+	})
+}
+
+#[test]
+fn on_finalize_completes_expired_proposals() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert!(ExpiringProposals::<Test>::contains_key(101, 1));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+
+		// Nothing happens before the end block
+		System::set_block_number(100);
+		Voting::on_finalize(100);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		System::assert_last_event((Event::VotingEnded { winner: Some(1) }).into());
+		assert!(!ExpiringProposals::<Test>::contains_key(101, 1));
+	})
+}
+
+#[test]
+fn votes_consume_reserved_tokens() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(create_proposal(voter, "Quadratic voting is fair!"));
+		assert_ok!(start_proposal_helper(voter, 3, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 25, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(1, voter), 5);
+		assert_eq!(Voting::get_available_reserve(&voter), 25);
+		assert_ok!(vote_proposal_with_tokens(voter, 2, 25, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(2, voter), 5);
+		assert_eq!(Voting::get_available_reserve(&voter), 0);
+		// The reservation is drained
+		assert_noop!(
+			vote_proposal_with_tokens(voter, 3, 1, Vote::Aye),
+			Error::<Test>::NotEnoughReservedTokens
+		);
+	})
+}
+
+#[test]
+fn cancel_a_proposal() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(voter), 1));
+		System::assert_last_event((Event::ProposalCancelled { proposal_index: 1 }).into());
+		assert!(!Voting::is_proposal_registered(1));
+		assert_noop!(
+			Voting::cancel_proposal(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::ProposalNotFound
+		);
+	})
+}
+
+#[test]
+fn try_to_cancel_a_proposal_of_another_voter() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_noop!(
+			Voting::cancel_proposal(RuntimeOrigin::signed(other_voter), 1),
+			Error::<Test>::NotProposalOwner
+		);
+		// Started proposals can't be cancelled, not even by their proposer
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_noop!(
+			Voting::cancel_proposal(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::ProposalAlreadyStarted
+		);
+	})
+}
+
+#[test]
+fn unregister_a_voter() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(other_voter, 49));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_winner(), Some(1));
+
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), other_voter));
+		System::assert_last_event((Event::VoterUnregistered { voter_id: other_voter }).into());
+		assert!(!Voting::is_voter_registered(&other_voter));
+		assert_eq!(Balances::reserved_balance(&other_voter), 0);
+		assert_eq!(Voting::get_free_voting_balance(&other_voter), 95);
+		// The votes of the removed voter don't count anymore
+		assert!(!AyeVotes::<Test>::contains_key(1, other_voter));
+		assert_eq!(ProposalVoterCount::<Test>::get(1), 0);
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 0);
+		assert_eq!(Voting::get_net_votes(1), 0);
+		assert_noop!(
+			Voting::unregister_voter(RawOrigin::Root.into(), other_voter),
+			Error::<Test>::NotRegisteredVoter
+		);
+	})
+}
+
+#[test]
+fn get_winner_is_none_without_votes() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Voting::get_winner(), None);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(Voting::get_winner(), None);
+		assert_eq!(Voting::get_proposal_status(2), Err(Error::<Test>::ProposalNotFound.into()));
+		// Ending the voting without any vote doesn't pick a winner
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		System::set_block_number(200);
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> = vec![(1, 10, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(voter, proposals));
+		System::assert_last_event((Event::VotingEndedWithoutWinner).into());
+	})
+}
+
+#[test]
+fn proposal_tally_matches_the_stored_votes() {
+	new_test_ext().execute_with(|| {
+		AllowRepeatVotes::set(&true);
+		let voters = [1, 2, 3, 4];
+		for voter in voters {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_ok!(create_proposal(1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(1, 1, 10));
+		assert_ok!(create_proposal(2, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(2, 2, 10));
+		for voter in voters {
+			assert_ok!(reserve_tokens_helper(voter, 60));
+		}
+		assert_ok!(vote_proposal_with_tokens(1, 1, 25, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(1, 1, 16, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(2, 1, 9, Vote::Nay));
+		assert_ok!(vote_proposal_with_tokens(3, 2, 36, Vote::Nay));
+		let proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)> =
+			vec![(1, 4, Vote::Aye), (2, 49, Vote::Aye)];
+		assert_ok!(vote_multiple_proposals_helper(4, proposals));
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), 2));
+
+		for proposal_index in [1, 2] {
+			let ayes: u128 = AyeVotes::<Test>::iter_prefix_values(proposal_index).sum();
+			let nays: u128 = NayVotes::<Test>::iter_prefix_values(proposal_index).sum();
+			assert_eq!(ProposalTally::<Test>::get(proposal_index), (ayes, nays));
+		}
+		assert_eq!(ProposalTally::<Test>::get(1), (11, 0));
+		assert_eq!(ProposalTally::<Test>::get(2), (7, 6));
+	})
+}
+
+#[test]
+fn migrate_proposal_tallies_to_v2() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Voting>();
+		AyeVotes::<Test>::insert(1, 1, 7);
+		AyeVotes::<Test>::insert(1, 2, 5);
+		NayVotes::<Test>::insert(1, 3, 4);
+
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(2));
+		assert_eq!(ProposalTally::<Test>::get(1), (12, 4));
+		assert_eq!(Voting::get_net_votes(1), 8);
+	})
+}
+
+#[test]
+fn query_current_winner_and_proposal_tally() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(Voting::proposal_tally(1), Some((0, 0)));
+		assert_eq!(Voting::proposal_tally(2), None);
+
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_ok!(reserve_tokens_helper(other_voter, 16));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Nay));
+		assert_eq!(Voting::proposal_tally(1), Some((7, 4)));
+		assert_eq!(Voting::get_winner(), Some(1));
+	})
+}
+
+#[test]
+fn register_voters_at_genesis() {
+	new_test_ext_with_voters(vec![(1, 5), (2, 10)]).execute_with(|| {
+		assert!(Voting::is_voter_registered(&1));
+		assert!(Voting::is_voter_registered(&2));
+		assert!(!Voting::is_voter_registered(&3));
+		assert_eq!(Voting::get_free_voting_balance(&1), 95);
+		assert_eq!(Voting::get_free_voting_balance(&2), 90);
+		// Genesis voters can't be registered again
+		assert_noop!(
+			Voting::register_voter(RawOrigin::Root.into(), 1, 5, false),
+			Error::<Test>::VoterAlreadyRegistered
+		);
+	})
+}
+
+#[test]
+fn create_a_proposal_with_the_longest_allowed_text() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		let text = "a".repeat(64);
+		assert_ok!(create_proposal(voter, &text));
+		assert_eq!(ProposalTexts::<Test>::get(1).unwrap().into_inner(), text.as_bytes().to_vec());
+		// The event still carries the hash of the text
+		let hashed_text: H256 = text.using_encoded(blake2_256).into();
+		System::assert_last_event(
+			Event::NewProposalCreated {
+				proposal_index: 1,
+				text: hashed_text,
+				end_block: 101,
+				created_at: 1,
+				metadata: None,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn try_to_create_a_proposal_with_a_too_long_text() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_noop!(create_proposal(voter, &"a".repeat(65)), Error::<Test>::ProposalTextTooLong);
+		assert!(!ProposalTexts::<Test>::contains_key(1));
+	})
+}
+
+#[test]
+fn change_a_vote_from_aye_to_nay() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_noop!(
+			Voting::change_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 25),
+			Error::<Test>::NotVoted
+		);
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 16, Vote::Aye));
+		assert_eq!(ProposalTally::<Test>::get(1), (4, 0));
+
+		assert_ok!(Voting::change_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 25));
+		assert!(!AyeVotes::<Test>::contains_key(1, voter));
+		assert_eq!(NayVotes::<Test>::get(1, voter), 5);
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 5));
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 25);
+		assert_eq!(Voting::get_available_reserve(&voter), 25);
+		System::assert_last_event(Event::VoteChanged { proposal_index: 1, vote: Vote::Nay }.into());
+	})
+}
+
+#[test]
+fn vote_with_tokens_delegated_through_a_two_level_chain() {
+	new_test_ext().execute_with(|| {
+		let voters = [1, 2, 3];
+		for voter in voters {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 20));
+		}
+		assert_ok!(create_proposal(1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(1, 1, 10));
+		// 3 -> 2 -> 1
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(3), 2));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::Delegated { who: 2, to: 1 }.into());
+		assert_eq!(Voting::get_available_reserve(&1), 60);
+
+		assert_noop!(
+			vote_proposal_with_tokens(2, 1, 20, Vote::Aye),
+			Error::<Test>::VotingPowerDelegated
+		);
+		assert_ok!(vote_proposal_with_tokens(1, 1, 60, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(1, 1), 7);
+		assert_eq!(Voting::get_available_reserve(&1), 0);
+
+		// The tokens lent by 3 are spent, taking them back would let them vote twice
+		assert_noop!(
+			Voting::undelegate(RuntimeOrigin::signed(3)),
+			Error::<Test>::DelegatedTokensCommitted
+		);
+		assert_noop!(
+			Voting::delegate(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::DelegatedTokensCommitted
+		);
+		assert_noop!(
+			Voting::unreserve_tokens(RuntimeOrigin::signed(3), 10),
+			Error::<Test>::VotingPowerDelegated
+		);
+
+		System::set_block_number(200);
+		assert_ok!(vote_proposal_with_tokens(1, 1, 10, Vote::Aye));
+		assert_ok!(Voting::claim_refund(RuntimeOrigin::signed(1), 1));
+		assert_ok!(Voting::undelegate(RuntimeOrigin::signed(3)));
+		assert_noop!(Voting::undelegate(RuntimeOrigin::signed(3)), Error::<Test>::NotDelegated);
+		assert!(Delegators::<Test>::get(2).is_empty());
+		assert_eq!(Voting::get_available_reserve(&1), 20);
+	})
+}
+
+#[test]
+fn try_to_delegate_in_a_cycle() {
+	new_test_ext().execute_with(|| {
+		let voters = [1, 2, 3];
+		for voter in voters {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(1), 1), Error::<Test>::DelegationCycle);
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(1), 2));
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(2), 1), Error::<Test>::DelegationCycle);
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 3));
+		assert_noop!(Voting::delegate(RuntimeOrigin::signed(3), 1), Error::<Test>::DelegationCycle);
+		assert_noop!(
+			Voting::delegate(RuntimeOrigin::signed(3), 4),
+			Error::<Test>::NotRegisteredVoter
+		);
+	})
+}
+
+#[test]
+fn commit_and_reveal_a_vote() {
+	new_test_ext().execute_with(|| {
+		RevealPeriod::set(&10);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		let salt = H256::repeat_byte(7);
+		let commitment: H256 = (Vote::Aye, 49u128, salt).using_encoded(blake2_256).into();
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(voter), 1, commitment));
+		System::assert_last_event(Event::VoteCommitted { proposal_index: 1 }.into());
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 0));
+		assert_noop!(
+			Voting::commit_vote(RuntimeOrigin::signed(voter), 1, commitment),
+			Error::<Test>::VoterAlreadyVoted
+		);
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49, salt),
+			Error::<Test>::RevealTooEarly
+		);
+
+		System::set_block_number(101);
+		assert_ok!(Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49, salt));
+		System::assert_last_event(
+			Event::VoteRevealed { proposal_index: 1, vote: Vote::Aye }.into(),
+		);
+		assert_eq!(AyeVotes::<Test>::get(1, voter), 7);
+		assert_eq!(ProposalTally::<Test>::get(1), (7, 0));
+		assert!(!VoteCommitments::<Test>::contains_key(1, voter));
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49, salt),
+			Error::<Test>::CommitmentNotFound
+		);
+	})
+}
+
+#[test]
+fn try_to_reveal_a_tampered_vote() {
+	new_test_ext().execute_with(|| {
+		RevealPeriod::set(&10);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		let salt = H256::repeat_byte(7);
+		let commitment: H256 = (Vote::Aye, 49u128, salt).using_encoded(blake2_256).into();
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(voter), 1, commitment));
+
+		System::set_block_number(101);
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 49, salt),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Aye, 36, salt),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49, H256::zero()),
+			Error::<Test>::InvalidReveal
+		);
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 0));
+	})
+}
+
+#[test]
+fn no_winner_just_below_the_quorum() {
+	new_test_ext().execute_with(|| {
+		MinQuorum::set(&2);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_winner(), None);
+
+		// The vote after the end completes the proposal
+		System::set_block_number(200);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		System::assert_last_event(Event::VotingEndedNoQuorum { proposal_index: 1 }.into());
+	})
+}
+
+#[test]
+fn winner_just_at_the_quorum() {
+	new_test_ext().execute_with(|| {
+		MinQuorum::set(&2);
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(reserve_tokens_helper(other_voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_winner(), Some(1));
+
+		// The vote after the end completes the proposal
+		System::set_block_number(200);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		System::assert_last_event(Event::VotingEnded { winner: Some(1) }.into());
+	})
+}
+
+#[test]
+fn claim_a_refund_once_the_proposal_completes() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_noop!(
+			Voting::claim_refund(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::ProposalNotCompleted
+		);
+
+		System::set_block_number(200);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_ok!(Voting::claim_refund(RuntimeOrigin::signed(voter), 1));
+		System::assert_last_event(
+			Event::VotesRefunded { proposal_index: 1, who: voter, amount: 49 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(&voter), 0);
+		assert_eq!(Balances::free_balance(&voter), 85);
+		assert_noop!(
+			Voting::claim_refund(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::NotReservedTokens
+		);
+	})
+}
+
+#[test]
+fn mint_the_winner_reward_to_the_proposer() {
+	new_test_ext().execute_with(|| {
+		WinnerReward::set(&20);
+		let proposer = 1;
+		let voter = 2;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		let balance_before = Balances::free_balance(&proposer);
+
+		System::set_block_number(200);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Balances::free_balance(&proposer), balance_before + 20);
+		System::assert_has_event(Event::ProposerRewarded { who: proposer, amount: 20 }.into());
+	})
+}
+
+#[test]
+fn no_winner_reward_without_a_winner() {
+	new_test_ext().execute_with(|| {
+		WinnerReward::set(&20);
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		let balance_before = Balances::free_balance(&proposer);
+
+		System::set_block_number(200);
+		assert_ok!(vote_proposal(proposer, 1, Vote::Aye));
+		assert_eq!(Balances::free_balance(&proposer), balance_before);
+		System::assert_last_event(Event::VotingEndedWithoutWinner.into());
+	})
+}
+
+#[test]
+fn fees_and_slashes_are_routed_to_the_fee_handler() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		// The registration fee is withheld from the initial balance, it isn't minted
+		assert_eq!(RoutedFees::get(), 0);
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(RoutedFees::get(), 10);
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(Voting::unreserve_tokens(RuntimeOrigin::signed(voter), 10));
+		assert_eq!(RoutedFees::get(), 15);
+	})
+}
+
+#[test]
+fn paying_fees_preserves_the_existing_balances() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		Balances::make_free_balance_be(&voter, 50);
+		assert_ok!(Balances::reserve(&voter, 20));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_eq!(Balances::free_balance(&voter), 125);
+		assert_eq!(Balances::reserved_balance(&voter), 20);
+
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(Balances::free_balance(&voter), 115);
+		assert_eq!(Balances::reserved_balance(&voter), 20);
+	})
+}
+
+#[test]
+fn create_proposals_up_to_max_proposals() {
+	new_test_ext().execute_with(|| {
+		MaxProposals::set(&2);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_eq!(Proposals::<Test>::count(), 2);
+	})
+}
+
+#[test]
+fn try_to_create_a_proposal_past_max_proposals() {
+	new_test_ext().execute_with(|| {
+		MaxProposals::set(&2);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_noop!(
+			create_proposal(voter, "Decentralization matters!"),
+			Error::<Test>::TooManyProposals
+		);
+		// Cancelling a proposal frees its slot
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(voter), 1));
+		assert_ok!(create_proposal(voter, "Decentralization matters!"));
+	})
+}
+
+#[test]
+fn create_a_proposal_with_a_custom_voting_period() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		let text = b"Let's use blockchain to create a better world!".to_vec();
+		assert_ok!(Voting::create_proposal(
+			RuntimeOrigin::signed(voter),
+			text.clone(),
+			Some(50),
+			None
+		));
+		assert_eq!(Voting::get_proposal_end_block(1), Ok(51));
+		// The bounds themselves are allowed
+		assert_ok!(Voting::create_proposal(
+			RuntimeOrigin::signed(voter),
+			text.clone(),
+			Some(10),
+			None
+		));
+		assert_ok!(Voting::create_proposal(RuntimeOrigin::signed(voter), text, Some(1_000), None));
+		assert_eq!(Voting::get_proposal_end_block(3), Ok(1_001));
+	})
+}
+
+#[test]
+fn try_to_create_a_proposal_with_an_out_of_range_voting_period() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		let text = b"Let's use blockchain to create a better world!".to_vec();
+		assert_noop!(
+			Voting::create_proposal(RuntimeOrigin::signed(voter), text.clone(), Some(9), None),
+			Error::<Test>::InvalidVotingPeriod
+		);
+		assert_noop!(
+			Voting::create_proposal(RuntimeOrigin::signed(voter), text, Some(1_001), None),
+			Error::<Test>::InvalidVotingPeriod
+		);
+	})
+}
+
+#[test]
+fn end_block_is_counted_from_the_start() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		System::set_block_number(100);
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(Voting::get_proposal_end_block(1), Ok(100 + VotingPeriod::get()));
+		assert!(ExpiringProposals::<Test>::contains_key(200, 1));
+		assert!(Voting::is_proposal_active(1));
+	})
+}
+
+#[test]
+fn voting_ended_emits_the_results_of_every_proposal() {
+	new_test_ext().execute_with(|| {
+		let voters = [1, 2, 3, 4];
+		for voter in voters {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 50));
+		}
+		assert_ok!(create_proposal(1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(1, 1, 10));
+		assert_ok!(create_proposal(2, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(2, 2, 10));
+		assert_ok!(vote_proposal_with_tokens(1, 1, 25, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(2, 1, 9, Vote::Nay));
+		assert_ok!(vote_proposal_with_tokens(3, 1, 1, Vote::Abstain));
+		assert_ok!(vote_proposal_with_tokens(4, 2, 16, Vote::Aye));
+
+		// Both proposals complete at the end of their last block
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		let results = vec![(1, 5, 3, 1), (2, 4, 0, 0)].try_into().unwrap();
+		System::assert_has_event(Event::ProposalResults { results }.into());
+		System::assert_last_event(Event::VotingEnded { winner: Some(2) }.into());
+	})
+}
+
+#[test]
+fn reject_a_started_proposal() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_winner(), Some(1));
+
+		assert_ok!(Voting::reject_proposal(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::ProposalRejected { proposal_index: 1 }.into());
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Rejected));
+		assert!(!Voting::is_proposal_active(1));
+		assert_eq!(Voting::get_winner(), None);
+
+		// The committed tokens are refundable
+		assert_ok!(Voting::claim_refund(RuntimeOrigin::signed(voter), 1));
+		assert_eq!(Balances::reserved_balance(&voter), 0);
+		assert_eq!(Balances::free_balance(&voter), 85);
+	})
+}
+
+#[test]
+fn try_to_vote_a_rejected_proposal() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_noop!(
+			Voting::reject_proposal(RuntimeOrigin::signed(voter), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Voting::reject_proposal(RuntimeOrigin::root(), 1));
+
+		assert_noop!(vote_proposal(voter, 1, Vote::Aye), Error::<Test>::ProposalNotActive);
+		assert_noop!(
+			Voting::reject_proposal(RuntimeOrigin::root(), 1),
+			Error::<Test>::ProposalAlreadyClosed
+		);
+	})
+}
+
+#[test]
+fn vote_with_credits_reserves_their_square() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+
+		assert_ok!(Voting::vote_with_credits(RuntimeOrigin::signed(voter), 1, Vote::Aye, 7));
+		assert_eq!(Balances::reserved_balance(&voter), 49);
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 49);
+		assert_eq!(AyeVotes::<Test>::get(1, voter), 7);
+	})
+}
+
+#[test]
+fn try_to_vote_with_credits_without_enough_balance() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+
+		// 10 credits cost 100 tokens, but only 85 are free
+		assert_noop!(
+			Voting::vote_with_credits(RuntimeOrigin::signed(voter), 1, Vote::Aye, 10),
+			Error::<Test>::NotEnoughBalance
+		);
+	})
+}
+
+#[test]
+fn active_proposals_lists_the_started_ones() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a fairer world!"));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a greener world!"));
+		assert!(Voting::active_proposals().is_empty());
+
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(start_proposal_helper(voter, 3, 10));
+		assert_eq!(Voting::active_proposals(), vec![1, 3]);
+	})
+}
+
+#[test]
+fn votes_of_lists_every_ballot_of_the_voter() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a fairer world!"));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a greener world!"));
+		for proposal_index in 1..=3 {
+			assert_ok!(start_proposal_helper(voter, proposal_index, 10));
+		}
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(reserve_tokens_helper(other_voter, 49));
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 16, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(voter, 2, 9, Vote::Nay));
+		assert_ok!(vote_proposal(voter, 3, Vote::Abstain));
+		assert_ok!(vote_proposal(other_voter, 1, Vote::Aye));
+
+		assert_eq!(
+			Voting::votes_of(&voter),
+			vec![(1, Vote::Aye, 4), (2, Vote::Nay, 3), (3, Vote::Abstain, 0)]
+		);
+		assert_eq!(Voting::votes_of(&other_voter), vec![(1, Vote::Aye, 7)]);
+	})
+}
+
+#[test]
+fn proposer_votes_own_proposal_when_self_voting_is_allowed() {
+	new_test_ext().execute_with(|| {
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(reserve_tokens_helper(proposer, 49));
+		assert_ok!(vote_proposal(proposer, 1, Vote::Aye));
+		assert_eq!(AyeVotes::<Test>::get(1, proposer), 7);
+	})
+}
+
+#[test]
+fn try_to_vote_own_proposal_when_self_voting_is_disallowed() {
+	new_test_ext().execute_with(|| {
+		AllowSelfVoting::set(&false);
+		let proposer = 1;
+		let voter = 2;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(reserve_tokens_helper(proposer, 49));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+
+		assert_noop!(vote_proposal(proposer, 1, Vote::Aye), Error::<Test>::CannotVoteOwnProposal);
+		assert_noop!(
+			vote_multiple_proposals_helper(proposer, vec![(1, 10, Vote::Aye)]),
+			Error::<Test>::CannotVoteOwnProposal
+		);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+	})
+}
+
+#[test]
+fn register_a_batch_of_voters() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(setup_new_voter(1, 5));
+		let voters = vec![(1, 10), (2, 5), (3, 5), (4, 5), (5, 5), (6, 5)];
+		assert_ok!(Voting::register_voters(RuntimeOrigin::root(), voters));
+		System::assert_last_event(Event::VotersRegistered { count: 5 }.into());
+		for voter in 2..=6 {
+			assert!(Voting::is_voter_registered(&voter));
+			System::assert_has_event(
+				Event::VoterRegistered { voter_id: voter, initial_balance: 95 }.into(),
+			);
+			assert_eq!(Balances::free_balance(&voter), 95);
+		}
+		// The voter already registered is skipped
+		assert_eq!(Balances::free_balance(&1), 95);
+	})
+}
+
+#[test]
+fn try_to_register_a_batch_of_too_many_voters() {
+	new_test_ext().execute_with(|| {
+		let voters = (1..=11).map(|voter| (voter, 5)).collect();
+		assert_noop!(
+			Voting::register_voters(RuntimeOrigin::root(), voters),
+			Error::<Test>::TooManyVoters
+		);
+	})
+}
+
+#[test]
+fn vote_multiple_proposals_emits_a_single_event() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 70));
+		assert_ok!(vote_multiple_proposals_helper(
+			voter,
+			vec![(1, 50, Vote::Aye), (2, 20, Vote::Aye)]
+		));
+
+		let proposals_voted_events: Vec<RuntimeEvent> = System::events()
+			.into_iter()
+			.map(|record| record.event)
+			.filter(|event| matches!(event, RuntimeEvent::Voting(Event::BatchVoteOutcome { .. })))
+			.collect();
+		assert_eq!(
+			proposals_voted_events,
+			vec![RuntimeEvent::Voting(Event::BatchVoteOutcome { succeeded: vec![1, 2] })]
+		);
+	})
+}
+
+#[test]
+fn try_to_vote_multiple_proposals_with_a_duplicate_proposal() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_noop!(
+			vote_multiple_proposals_helper(voter, vec![(1, 10, Vote::Aye), (1, 5, Vote::Aye)]),
+			Error::<Test>::DuplicateProposalInBatch
+		);
+	})
+}
+
+#[test]
+fn close_an_expired_proposal_without_voting() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_noop!(
+			Voting::close_proposal(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::VotingStillActive
+		);
+
+		System::set_block_number(200);
+		assert_ok!(Voting::close_proposal(RuntimeOrigin::signed(voter), 1));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		System::assert_last_event(Event::VotingEndedWithoutWinner.into());
+		assert_noop!(
+			Voting::close_proposal(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::ProposalNotActive
+		);
+	})
+}
+
+#[test]
+fn nay_majority_vetoes_the_proposal_with_the_most_ayes() {
+	new_test_ext().execute_with(|| {
+		let voter_1 = 1;
+		let voter_2 = 2;
+		let voter_3 = 3;
+		assert_ok!(setup_new_voter(voter_1, 5));
+		assert_ok!(setup_new_voter(voter_2, 5));
+		assert_ok!(setup_new_voter(voter_3, 5));
+		assert_ok!(create_proposal(voter_1, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter_2, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter_1, 1, 10));
+		assert_ok!(start_proposal_helper(voter_2, 2, 10));
+
+		// Proposal 1: 7 ayes, 8 nays
+		assert_ok!(reserve_tokens_helper(voter_1, 49));
+		assert_ok!(vote_proposal(voter_1, 1, Vote::Aye));
+		assert_ok!(reserve_tokens_helper(voter_2, 64));
+		assert_ok!(vote_proposal(voter_2, 1, Vote::Nay));
+		// Proposal 2: 4 ayes
+		assert_ok!(reserve_tokens_helper(voter_3, 16));
+		assert_ok!(vote_proposal(voter_3, 2, Vote::Aye));
+
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		System::assert_has_event(Event::ProposalVetoed { proposal_index: 1 }.into());
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Vetoed));
+		assert_eq!(Voting::get_proposal_status(2), Ok(ProposalStatus::Completed));
+		assert_eq!(Voting::get_winner(), Some(2));
+		System::assert_last_event(Event::VotingEnded { winner: Some(2) }.into());
+	})
+}
+
+#[test]
+fn proposal_created_block_is_the_block_at_creation() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		System::set_block_number(7);
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_eq!(Voting::get_proposal_created_block(1), Ok(7));
+
+		// Starting the proposal keeps the block it was created at
+		System::set_block_number(12);
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(Voting::get_proposal_created_block(1), Ok(7));
+		assert_eq!(
+			Voting::get_proposal_created_block(2),
+			Err(Error::<Test>::ProposalNotFound.into())
+		);
+	})
+}
+
+#[test]
+fn voting_ends_without_winner_when_only_nays_are_cast() {
+	new_test_ext().execute_with(|| {
+		let proposer = 1;
+		let voter = 2;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Nay));
+
+		System::set_block_number(200);
+		assert_ok!(Voting::close_proposal(RuntimeOrigin::signed(voter), 1));
+		assert_eq!(Voting::get_winner(), None);
+		System::assert_last_event(Event::VotingEndedWithoutWinner.into());
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, RuntimeEvent::Voting(Event::VotingEnded { .. }))));
+	})
+}
+
+#[test]
+fn add_votes_to_an_already_voted_proposal() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_aye_votes_balance(1, &voter), 7);
+
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49));
+		System::assert_last_event(
+			Event::ProposalVoted { proposal_index: 1, vote: Vote::Aye }.into(),
+		);
+		assert_eq!(Voting::get_aye_votes_balance(1, &voter), 14);
+		assert_eq!(Voting::proposal_tally(1), Some((14, 0)));
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 98);
+		assert_eq!(Voting::get_available_reserve(&voter), 0);
+	})
+}
+
+#[test]
+fn try_to_add_votes_on_the_other_side() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_noop!(
+			Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49),
+			Error::<Test>::NotVoted
+		);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+
+		assert_ok!(reserve_tokens_helper(voter, 25));
+		assert_noop!(
+			Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Nay, 25),
+			Error::<Test>::VoteDirectionMismatch
+		);
+		assert_noop!(
+			Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Aye, 26),
+			Error::<Test>::NotEnoughReservedTokens
+		);
+	})
+}
+
+#[test]
+fn vote_up_to_the_max_votes_per_voter() {
+	new_test_ext().execute_with(|| {
+		MaxVotesPerVoter::set(&7);
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_aye_votes_balance(1, &voter), 7);
+
+		// 64 tokens are 8 votes, one more than the cap
+		assert_ok!(reserve_tokens_helper(other_voter, 64));
+		assert_noop!(vote_proposal(other_voter, 1, Vote::Nay), Error::<Test>::VoteCapExceeded);
+	})
+}
+
+#[test]
+fn try_to_add_votes_over_the_max_votes_per_voter() {
+	new_test_ext().execute_with(|| {
+		MaxVotesPerVoter::set(&7);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 36));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_aye_votes_balance(1, &voter), 6);
+
+		assert_ok!(reserve_tokens_helper(voter, 4));
+		assert_noop!(
+			Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Aye, 4),
+			Error::<Test>::VoteCapExceeded
+		);
+		assert_ok!(Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Aye, 1));
+		assert_eq!(Voting::get_aye_votes_balance(1, &voter), 7);
+	})
+}
+
+#[test]
+fn get_reserved_tokens_and_free_voting_balance() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_eq!(Voting::get_reserved_tokens(&voter), 0);
+		assert_eq!(Voting::get_free_voting_balance(&voter), 95);
+
+		assert_ok!(reserve_tokens_helper(voter, 30));
+		assert_eq!(Voting::get_reserved_tokens(&voter), 30);
+		assert_eq!(Voting::get_free_voting_balance(&voter), 65);
+		#[allow(deprecated)]
+		let voter_balance = Voting::get_voter_balance(&voter);
+		assert_eq!(voter_balance, 65);
+	})
+}
+
+#[test]
+fn refunds_and_penalized_unreserves_emit_different_events() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(reserve_tokens_helper(voter, 30));
+		assert_ok!(reserve_tokens_helper(other_voter, 30));
+
+		// Unreserving early is penalized
+		assert_ok!(unreserve_tokens_helper(voter, 10));
+		System::assert_last_event(
+			Event::TokensUnreserved { who: voter, amount: 10, updated_balance: 70 }.into(),
+		);
+
+		// Unregistering gives the tokens back untouched
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), other_voter));
+		System::assert_has_event(Event::TokensRefunded { who: other_voter, amount: 30 }.into());
+		assert_eq!(Voting::get_free_voting_balance(&other_voter), 95);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Voting(Event::TokensRefunded { who, .. }) if who == voter
+		)));
+	})
+}
+
+#[test]
+fn vote_the_options_of_a_multi_choice_proposal() {
+	new_test_ext().execute_with(|| {
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Which funding level should we pick?"));
+		let options: Vec<H256> = (1..=3).map(H256::repeat_byte).collect();
+		assert_ok!(Voting::set_proposal_options(RuntimeOrigin::signed(proposer), 1, options));
+		System::assert_last_event(Event::ProposalOptionsSet { proposal_index: 1, count: 3 }.into());
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_eq!(Voting::get_winning_option(1), None);
+
+		for voter in 2..=4 {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_ok!(reserve_tokens_helper(2, 49));
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(2), 1, 0, 49));
+		System::assert_last_event(Event::OptionVoted { proposal_index: 1, option_index: 0 }.into());
+		// Spreading the tokens weights each option on its own
+		assert_ok!(reserve_tokens_helper(3, 40));
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(3), 1, 1, 36));
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(3), 1, 0, 4));
+		assert_ok!(reserve_tokens_helper(4, 64));
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(4), 1, 2, 64));
+
+		assert_eq!(Voting::get_option_votes(1, 0), 9);
+		assert_eq!(Voting::get_option_votes(1, 1), 6);
+		assert_eq!(Voting::get_option_votes(1, 2), 8);
+		assert_eq!(Voting::get_winning_option(1), Some(0));
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 153);
+		assert_eq!(Voting::get_available_reserve(&3), 0);
+	})
+}
+
+#[test]
+fn try_to_vote_an_invalid_option() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Which funding level should we pick?"));
+		assert_noop!(
+			Voting::set_proposal_options(
+				RuntimeOrigin::signed(voter),
+				1,
+				(1..=4).map(H256::repeat_byte).collect()
+			),
+			Error::<Test>::TooManyOptions
+		);
+		let options: Vec<H256> = (1..=3).map(H256::repeat_byte).collect();
+		assert_ok!(Voting::set_proposal_options(RuntimeOrigin::signed(voter), 1, options));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_noop!(
+			Voting::set_proposal_options(RuntimeOrigin::signed(voter), 1, vec![]),
+			Error::<Test>::ProposalAlreadyStarted
+		);
+
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_noop!(
+			Voting::vote_option(RuntimeOrigin::signed(voter), 1, 3, 49),
+			Error::<Test>::InvalidOption
+		);
+		assert_noop!(
+			Voting::vote_option(RuntimeOrigin::signed(voter), 1, 0, 50),
+			Error::<Test>::NotEnoughReservedTokens
+		);
+	})
+}
+
+#[test]
+fn migrate_proposal_options_to_v3() {
+	new_test_ext().execute_with(|| {
+		let text: H256 = "Let's use blockchain to create a better world!"
+			.using_encoded(blake2_256)
+			.into();
+		// Proposal stored with the layout previous to v3
+		let old_proposal =
+			(1u32, text, 1u64, 101u64, 1u64, 10u128, 1u64, None::<u64>, ProposalStatus::InProgress);
+		frame_support::storage::unhashed::put(&Proposals::<Test>::hashed_key_for(1), &old_proposal);
+		StorageVersion::new(2).put::<Voting>();
+
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(3));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+		assert!(Voting::get_proposal(1).unwrap().options.is_empty());
+		assert_eq!(Voting::get_winning_option(1), None);
+	})
+}
+
+#[test]
+fn both_voting_paths_handle_an_expired_proposal_identically() {
+	let vote_expired_proposal = |vote_multiple: bool| {
+		new_test_ext().execute_with(|| {
+			let voter = 1;
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+			assert_ok!(start_proposal_helper(voter, 1, 10));
+			assert_ok!(reserve_tokens_helper(voter, 49));
+			System::reset_events();
+
+			System::set_block_number(200);
+			if vote_multiple {
+				assert_ok!(vote_multiple_proposals_helper(voter, vec![(1, 49, Vote::Aye)]));
+			} else {
+				assert_ok!(Voting::vote_proposal(
+					RuntimeOrigin::signed(voter),
+					1,
+					49,
+					Vote::Aye,
+					0
+				));
+			}
+			// The vote isn't counted, the proposal is completed instead
+			assert!(!AyeVotes::<Test>::contains_key(1, voter));
+			assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+			System::events().into_iter().map(|record| record.event).collect::<Vec<_>>()
+		})
+	};
+	assert_eq!(vote_expired_proposal(true), vote_expired_proposal(false));
+}
+
+#[test]
+fn force_start_a_proposal_without_fee() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_noop!(
+			Voting::force_start_proposal(RuntimeOrigin::signed(voter), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Voting::force_start_proposal(RawOrigin::Root.into(), 2),
+			Error::<Test>::ProposalNotFound
+		);
+
+		System::set_block_number(5);
+		assert_ok!(Voting::force_start_proposal(RawOrigin::Root.into(), 1));
+		System::assert_last_event(
+			Event::ProposalStarted { proposal_index: 1, end_block: 105 }.into(),
+		);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+		assert_eq!(Voting::get_proposal_start_block(1), Ok(5));
+		assert_eq!(Voting::get_proposal_end_block(1), Ok(105));
+		// The proposer didn't pay anything
+		assert_eq!(Balances::free_balance(&voter), 95);
+
+		assert_noop!(
+			Voting::force_start_proposal(RawOrigin::Root.into(), 1),
+			Error::<Test>::ProposalAlreadyStarted
+		);
+	})
+}
+
+#[test]
+fn voter_count_counts_each_account_once() {
+	new_test_ext().execute_with(|| {
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_eq!(Voting::voter_count(1), 0);
+
+		for voter in 2..=4 {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 25));
+		}
+		assert_ok!(vote_proposal(2, 1, Vote::Aye));
+		assert_ok!(vote_proposal(3, 1, Vote::Nay));
+		assert_ok!(vote_proposal(4, 1, Vote::Abstain));
+		assert_eq!(Voting::voter_count(1), 3);
+
+		// Topping up doesn't add a voter
+		assert_ok!(reserve_tokens_helper(2, 24));
+		assert_ok!(Voting::add_votes(RuntimeOrigin::signed(2), 1, Vote::Aye, 24));
+		assert_eq!(Voting::voter_count(1), 3);
+
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), 3));
+		assert_eq!(Voting::voter_count(1), 2);
+	})
+}
+
+#[test]
+fn voting_fails_while_paused() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_noop!(
+			Voting::pause(RuntimeOrigin::signed(voter)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(Voting::pause(RawOrigin::Root.into()));
+		System::assert_last_event(Event::PalletPaused.into());
+		assert!(Voting::is_paused());
+		assert_noop!(
+			create_proposal(voter, "Let's use blockchain to create a better world!"),
+			Error::<Test>::PalletPaused
+		);
+		assert_noop!(start_proposal_helper(voter, 2, 10), Error::<Test>::PalletPaused);
+		assert_noop!(vote_proposal(voter, 1, Vote::Aye), Error::<Test>::PalletPaused);
+		assert_noop!(
+			vote_multiple_proposals_helper(voter, vec![(1, 49, Vote::Aye)]),
+			Error::<Test>::PalletPaused
+		);
+		// Reads and unreserves keep working
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+		assert_ok!(unreserve_tokens_helper(voter, 10));
+
+		assert_ok!(Voting::resume(RawOrigin::Root.into()));
+		System::assert_last_event(Event::PalletResumed.into());
+		assert!(!Voting::is_paused());
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_aye_votes_balance(1, &voter), 6);
+	})
+}
+
+#[test]
+fn reserve_tokens_at_the_minimum_reserve_amount() {
+	new_test_ext().execute_with(|| {
+		MinReserveAmount::set(&10);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_noop!(reserve_tokens_helper(voter, 9), Error::<Test>::ReserveBelowMinimum);
+		assert_ok!(reserve_tokens_helper(voter, 10));
+		System::assert_last_event(Event::TokensReserved { who: voter, amount: 10 }.into());
+		assert_eq!(Voting::get_reserved_tokens(&voter), 10);
+	})
+}
+
+#[test]
+fn abstentions_push_a_proposal_over_the_quorum() {
+	new_test_ext().execute_with(|| {
+		MinQuorum::set(&3);
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		for voter in 2..=4 {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 49));
+		}
+		assert_ok!(vote_proposal(2, 1, Vote::Aye));
+		assert_ok!(vote_proposal(3, 1, Vote::Abstain));
+		assert!(!Voting::quorum_reached(1));
+		assert_eq!(Voting::get_winner(), None);
+
+		assert_ok!(vote_proposal(4, 1, Vote::Abstain));
+		assert!(Voting::quorum_reached(1));
+		// The abstentions don't change the result
+		assert_eq!(Voting::get_net_votes(1), 7);
+		assert_eq!(Voting::get_winner(), Some(1));
+	})
+}
+
+#[test]
+fn create_a_proposal_with_metadata() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		let text = b"Let's use blockchain to create a better world!".to_vec();
+		let metadata =
+			b"ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec();
+		assert_noop!(
+			Voting::create_proposal(
+				RuntimeOrigin::signed(voter),
+				text.clone(),
+				None,
+				Some(metadata.clone())
+			),
+			Error::<Test>::MetadataTooLong
+		);
+
+		let metadata = b"ipfs://bafybeigdyrzt5sfp7udm7hu7".to_vec();
+		assert_ok!(Voting::create_proposal(
+			RuntimeOrigin::signed(voter),
+			text.clone(),
+			None,
+			Some(metadata.clone())
+		));
+		System::assert_last_event(
+			Event::NewProposalCreated {
+				proposal_index: 1,
+				text: text.using_encoded(blake2_256).into(),
+				end_block: 101,
+				created_at: 1,
+				metadata: Some(metadata.clone().try_into().unwrap()),
+			}
+			.into(),
+		);
+		assert_eq!(Voting::get_proposal(1).unwrap().metadata.unwrap().into_inner(), metadata);
+	})
+}
+
+#[test]
+fn create_a_proposal_without_metadata() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_eq!(Voting::get_proposal(1).unwrap().metadata, None);
+	})
+}
+
+#[test]
+fn migrate_proposal_metadata_to_v4() {
+	new_test_ext().execute_with(|| {
+		let text: H256 = "Let's use blockchain to create a better world!"
+			.using_encoded(blake2_256)
+			.into();
+		// Proposal stored with the layout previous to v4
+		let options: Vec<H256> = vec![H256::repeat_byte(1)];
+		let old_proposal = (
+			(1u32, text, 1u64, 101u64, 1u64, 10u128, 1u64, None::<u64>),
+			(ProposalStatus::InProgress, options.clone()),
+		);
+		frame_support::storage::unhashed::put(&Proposals::<Test>::hashed_key_for(1), &old_proposal);
+		StorageVersion::new(3).put::<Voting>();
+
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(4));
+		let proposal = Voting::get_proposal(1).unwrap();
+		assert_eq!(proposal.options.into_inner(), options);
+		assert_eq!(proposal.metadata, None);
+	})
+}
+
+#[test]
+fn an_invalid_vote_rolls_back_the_whole_batch() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 70));
+		let balance = Voting::get_free_voting_balance(&voter);
+		let available_reserve = Voting::get_available_reserve(&voter);
+
+		// The last vote of the batch is invalid
+		assert_noop!(
+			vote_multiple_proposals_helper(voter, vec![(1, 10, Vote::Aye), (2, 5, Vote::Nay)]),
+			Error::<Test>::VoteNotSupportedInBatch
+		);
+		assert!(!AyeVotes::<Test>::contains_key(1, voter));
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 0));
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 0);
+		assert_eq!(Voting::get_free_voting_balance(&voter), balance);
+		assert_eq!(Voting::get_available_reserve(&voter), available_reserve);
+	})
+}
+
+#[test]
+fn create_proposal_pays_the_creation_fee() {
+	new_test_ext().execute_with(|| {
+		ProposalCreationFee::set(&20);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		let balance = Voting::get_free_voting_balance(&voter);
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_eq!(Voting::get_free_voting_balance(&voter), balance - 20);
+		assert_eq!(RoutedFees::get(), 20);
+		assert!(System::events().iter().any(|record| record.event ==
+			RuntimeEvent::Voting(Event::ProposalFeePaid { who: voter, amount: 20 })));
+	})
+}
+
+#[test]
+fn try_to_create_a_proposal_without_enough_balance_for_the_fee() {
+	new_test_ext().execute_with(|| {
+		ProposalCreationFee::set(&1_000);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_noop!(
+			create_proposal(voter, "Let's use blockchain to create a better world!"),
+			Error::<Test>::NotEnoughBalance
+		);
+		assert!(!Voting::is_proposal_registered(1));
+	})
+}
+
+#[test]
+fn proposal_deposit_is_returned_when_quorum_is_reached() {
+	new_test_ext().execute_with(|| {
+		ProposalDeposit::set(&20);
+		MinQuorum::set(&1);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_eq!(Voting::get_reserved_tokens(&voter), 20);
+		assert_eq!(HeldDeposits::<Test>::get(voter), 20);
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 30));
+		// The deposit can't be used to vote
+		assert_eq!(Voting::get_available_reserve(&voter), 30);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+
+		System::set_block_number(200);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		System::assert_has_event(
+			Event::ProposalDepositReturned { proposal_index: 1, amount: 20 }.into(),
+		);
+		assert_eq!(HeldDeposits::<Test>::get(voter), 0);
+		assert_eq!(Voting::get_reserved_tokens(&voter), 30);
+		assert_eq!(Voting::get_proposal(1).unwrap().deposit, 0);
+	})
+}
+
+#[test]
+fn proposal_deposit_is_slashed_when_quorum_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		ProposalDeposit::set(&20);
+		MinQuorum::set(&1);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		let balance = Balances::total_balance(&voter);
+
+		System::set_block_number(200);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		System::assert_has_event(
+			Event::ProposalDepositSlashed { proposal_index: 1, amount: 20 }.into(),
+		);
+		assert_eq!(Balances::total_balance(&voter), balance - 20);
+		assert_eq!(Voting::get_reserved_tokens(&voter), 0);
+		assert_eq!(RoutedFees::get(), 30);
+	})
+}
+
+#[test]
+fn proposal_deposit_is_slashed_when_the_proposal_is_rejected() {
+	new_test_ext().execute_with(|| {
+		ProposalDeposit::set(&20);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(Voting::reject_proposal(RuntimeOrigin::root(), 1));
+		System::assert_has_event(
+			Event::ProposalDepositSlashed { proposal_index: 1, amount: 20 }.into(),
+		);
+		assert_eq!(Voting::get_reserved_tokens(&voter), 0);
+		assert_eq!(HeldDeposits::<Test>::get(voter), 0);
+	})
+}
+
+#[test]
+fn migrate_proposal_deposit_to_v5() {
+	new_test_ext().execute_with(|| {
+		let text: H256 = "Let's use blockchain to create a better world!"
+			.using_encoded(blake2_256)
+			.into();
+		// Proposal stored with the layout previous to v5
+		let old_proposal = (
+			(1u32, text, 1u64, 101u64, 1u64, 10u128, 1u64, None::<u64>),
+			(ProposalStatus::InProgress, Vec::<H256>::new(), None::<Vec<u8>>),
+		);
+		frame_support::storage::unhashed::put(&Proposals::<Test>::hashed_key_for(1), &old_proposal);
+		StorageVersion::new(4).put::<Voting>();
+
+		migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(5));
+		assert_eq!(Voting::get_proposal(1).unwrap().deposit, 0);
+	})
+}
+
+#[test]
+fn a_proposal_created_after_a_cancellation_gets_a_fresh_index() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(voter), 1));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert!(!Voting::is_proposal_registered(1));
+		assert!(Voting::is_proposal_registered(2));
+		assert_eq!(NextProposalIndex::<Test>::get(), 3);
+	})
+}
+
+#[test]
+fn try_to_create_a_proposal_with_the_indexes_exhausted() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		NextProposalIndex::<Test>::put(ProposalIndex::MAX);
+		assert_noop!(
+			create_proposal(voter, "Let's use blockchain to create a better world!"),
+			Error::<Test>::StorageOverflow
+		);
+	})
+}
+
+#[test]
+fn voting_is_open_only_for_started_proposals_before_their_end_block() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert!(!Voting::is_voting_open(1));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		// Not started
+		assert!(!Voting::is_voting_open(1));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert!(Voting::is_voting_open(1));
+		assert_ok!(Voting::pause(RuntimeOrigin::root()));
+		assert!(!Voting::is_voting_open(1));
+		assert_ok!(Voting::resume(RuntimeOrigin::root()));
+		assert!(Voting::is_voting_open(1));
+
+		// Expired, but not completed yet
+		System::set_block_number(101);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+		assert!(!Voting::is_voting_open(1));
+		// Completed
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert!(!Voting::is_voting_open(1));
+	})
+}
+
+#[test]
+fn voting_is_not_open_for_rejected_proposals() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(Voting::reject_proposal(RuntimeOrigin::root(), 1));
+		assert!(!Voting::is_voting_open(1));
+	})
+}
+
+#[test]
+fn vote_proposal_conviction_multiplies_the_votes() {
+	new_test_ext().execute_with(|| {
+		let voters = [1, 2, 3];
+		for voter in voters {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_ok!(create_proposal(1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(1, 1, 10));
+		// sqrt(16) = 4, multiplied by 1, 2 and 4
+		for (conviction, voter) in voters.into_iter().enumerate() {
+			assert_ok!(reserve_tokens_helper(voter, 20));
+			assert_ok!(Voting::vote_proposal(
+				RuntimeOrigin::signed(voter),
+				1,
+				16,
+				Vote::Aye,
+				conviction as u8
+			));
+		}
+		assert_eq!(AyeVotes::<Test>::get(1, 1), 4);
+		assert_eq!(AyeVotes::<Test>::get(1, 2), 8);
+		assert_eq!(AyeVotes::<Test>::get(1, 3), 16);
+		// The lock lasts as many lock periods as the multiplier
+		assert_eq!(ReserveLocks::<Test>::get(1), None);
+		assert_eq!(ReserveLocks::<Test>::get(2), Some((16, 21)));
+		assert_eq!(ReserveLocks::<Test>::get(3), Some((16, 41)));
+		System::assert_has_event(Event::TokensLocked { who: 3, amount: 16, until: 41 }.into());
+	})
+}
+
+#[test]
+fn try_to_vote_proposal_with_an_unknown_conviction() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 20));
+		assert_noop!(
+			Voting::vote_proposal(RuntimeOrigin::signed(voter), 1, 16, Vote::Aye, 3),
+			Error::<Test>::ConvictionTooHigh
+		);
+	})
+}
+
+#[test]
+fn try_to_unreserve_or_refund_tokens_locked_by_a_conviction_vote() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(Voting::vote_proposal(RuntimeOrigin::signed(voter), 1, 16, Vote::Aye, 1));
+		assert_noop!(
+			Voting::unreserve_tokens(RuntimeOrigin::signed(voter), 40),
+			Error::<Test>::TokensLocked
+		);
+		// The tokens out of the lock can still be unreserved
+		assert_ok!(Voting::unreserve_tokens(RuntimeOrigin::signed(voter), 10));
+		assert_ok!(Voting::reject_proposal(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Voting::claim_refund(RuntimeOrigin::signed(voter), 1),
+			Error::<Test>::TokensLocked
+		);
+
+		System::set_block_number(21);
+		assert_eq!(Voting::locked_reserve(&voter), 0);
+		assert_ok!(Voting::claim_refund(RuntimeOrigin::signed(voter), 1));
+		assert_eq!(Voting::get_reserved_tokens(&voter), 24);
+	})
+}
+
+#[test]
+fn on_finalize_emits_one_completion_per_finalized_proposal() {
+	new_test_ext().execute_with(|| {
+		let voters = [1, 2];
+		for voter in voters {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 50));
+		}
+		assert_ok!(create_proposal(1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(1, 1, 10));
+		assert_ok!(create_proposal(2, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(2, 2, 10));
+		assert_ok!(vote_proposal_with_tokens(1, 1, 25, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(2, 1, 9, Vote::Nay));
+		assert_ok!(vote_proposal_with_tokens(2, 2, 16, Vote::Aye));
+
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		// Expiring proposals aren't drained by index, so the order isn't checked
+		let mut completed = Vec::new();
+		for record in System::events() {
+			if let RuntimeEvent::Voting(Event::ProposalCompleted { proposal_index, ayes, nays }) =
+				record.event
+			{
+				completed.push((proposal_index, ayes, nays));
+			}
+		}
+		completed.sort();
+		assert_eq!(completed, vec![(1, 5, 3), (2, 4, 0)]);
+	})
+}
+
+#[test]
+fn proposals_by_lists_the_proposals_of_each_proposer() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let other_voter = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(other_voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(other_voter, "Blockchain is the future!"));
+		assert_ok!(create_proposal(voter, "Decentralization matters!"));
+		assert_eq!(Voting::proposals_by(&voter), vec![1, 3]);
+		assert_eq!(Voting::proposals_by(&other_voter), vec![2]);
+		assert!(Voting::proposals_by(&3).is_empty());
+		// Cancelled proposals are no longer listed
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(voter), 1));
+		assert_eq!(Voting::proposals_by(&voter), vec![3]);
+	})
+}
+
+#[test]
+fn start_a_proposal_with_the_minimum_proposer_reserve() {
+	new_test_ext().execute_with(|| {
+		MinProposerReserve::set(&30);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(reserve_tokens_helper(voter, 30));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+	})
+}
+
+#[test]
+fn try_to_start_a_proposal_below_the_minimum_proposer_reserve() {
+	new_test_ext().execute_with(|| {
+		MinProposerReserve::set(&30);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(reserve_tokens_helper(voter, 29));
+		assert_noop!(
+			start_proposal_helper(voter, 1, 10),
+			Error::<Test>::InsufficientProposerReserve
+		);
+	})
+}
+
+#[test]
+fn try_to_vote_proposal_past_the_max_votes_balance() {
+	new_test_ext().execute_with(|| {
+		AllowRepeatVotes::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 16, Vote::Aye));
+		AyeVotes::<Test>::insert(1, voter, u128::MAX - 1);
+		assert_noop!(
+			vote_proposal_with_tokens(voter, 1, 16, Vote::Aye),
+			Error::<Test>::AdditionOverflow
+		);
+	})
+}
+
+#[test]
+fn a_late_vote_completes_the_proposal_without_being_counted() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+
+		System::set_block_number(101);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		System::assert_has_event(Event::LateVoteIgnored { proposal_index: 1, who: voter }.into());
+		assert!(!AyeVotes::<Test>::contains_key(1, voter));
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 0));
+	})
+}
+
+#[test]
+fn extend_a_proposal_and_vote_after_the_original_deadline() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_noop!(
+			Voting::extend_proposal(RuntimeOrigin::root(), 1, 50),
+			Error::<Test>::ProposalNotActive
+		);
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_noop!(
+			Voting::extend_proposal(RuntimeOrigin::signed(voter), 1, 50),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(Voting::extend_proposal(RuntimeOrigin::root(), 1, 50));
+		System::assert_last_event(
+			Event::ProposalExtended { proposal_index: 1, new_end_block: 151 }.into(),
+		);
+		assert_eq!(Voting::get_proposal_end_block(1), Ok(151));
+
+		// The original end block no longer completes the proposal
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+
+		System::set_block_number(120);
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		assert!(AyeVotes::<Test>::contains_key(1, voter));
+
+		System::set_block_number(151);
+		Voting::on_finalize(151);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+	})
+}
+
+#[test]
+fn a_fee_that_would_reap_the_voter_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_eq!(Balances::free_balance(&voter), 95);
+
+		// Paying the whole balance would leave the account below the existential deposit
+		assert_noop!(start_proposal_helper(voter, 1, 95), Error::<Test>::NotEnoughBalance);
+		assert_ok!(start_proposal_helper(voter, 1, 94));
+		assert_eq!(Balances::free_balance(&voter), 1);
+		assert!(System::account_exists(&voter));
+	})
+}
+
+#[test]
+fn total_votes_for_proposal_matches_the_votes_of_each_voter() {
+	new_test_ext().execute_with(|| {
+		let voters = [1, 2, 3];
+		for voter in voters {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_ok!(create_proposal(1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(1, 1, 10));
+		for voter in voters {
+			assert_ok!(reserve_tokens_helper(voter, 60));
+		}
+		assert_eq!(Voting::total_votes_for_proposal(1), 0);
+		assert_ok!(vote_proposal_with_tokens(1, 1, 49, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(2, 1, 16, Vote::Aye));
+		assert_ok!(vote_proposal_with_tokens(3, 1, 25, Vote::Nay));
+
+		let ayes: u128 = AyeVotes::<Test>::iter_prefix_values(1).sum();
+		let nays: u128 = NayVotes::<Test>::iter_prefix_values(1).sum();
+		assert_eq!(Voting::total_votes_for_proposal(1), ayes - nays);
+		assert_eq!(Voting::total_votes_for_proposal(1), 6);
+	})
+}
+
+#[test]
+fn re_register_an_unregistered_voter() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(reserve_tokens_helper(voter, 20));
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), voter));
+		assert_eq!(Voting::get_free_voting_balance(&voter), 95);
+
+		// A new initial balance is credited on top of the tokens the account kept
+		assert_ok!(Voting::register_voter(RawOrigin::Root.into(), voter, 10, false));
+		assert!(Voting::is_voter_registered(&voter));
+		assert_eq!(Voting::get_free_voting_balance(&voter), 185);
+		assert_eq!(RoutedFees::get(), 0);
+	})
+}
+
+#[test]
+fn re_register_a_voter_preserving_its_balance() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), voter));
+		System::reset_events();
+
+		assert_ok!(Voting::register_voter(RawOrigin::Root.into(), voter, 10, true));
+		assert!(Voting::is_voter_registered(&voter));
+		assert_eq!(Voting::get_free_voting_balance(&voter), 95);
+		// No fee is collected when the balance is preserved
+		System::assert_last_event(
+			Event::VoterRegistered { voter_id: voter, initial_balance: 95 }.into(),
+		);
+		assert_eq!(System::events().len(), 1);
+	})
+}
+
+#[test]
+fn a_reopened_winner_is_not_rewarded_twice() {
+	new_test_ext().execute_with(|| {
+		WinnerReward::set(&20);
+		PoolReward::set(&15);
+		let proposer = 1;
+		let voter = 2;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(Voting::fund_reward_pool(RuntimeOrigin::signed(voter), 40));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		assert_ok!(vote_proposal(voter, 1, Vote::Aye));
+		let balance_before = Balances::free_balance(&proposer);
+
+		System::set_block_number(101);
+		assert_ok!(Voting::close_proposal(RuntimeOrigin::signed(voter), 1));
+		assert_eq!(Balances::free_balance(&proposer), balance_before + 20 + 15);
+
+		// Completing again after a dispute doesn't pay the rewards a second time
+		assert_ok!(Voting::invalidate_result(RawOrigin::Root.into(), 1));
+		System::set_block_number(201);
+		assert_ok!(Voting::close_proposal(RuntimeOrigin::signed(voter), 1));
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(Balances::free_balance(&proposer), balance_before + 20 + 15);
+		assert_eq!(Voting::reward_pool_balance(), 25);
+	})
+}
+
+#[test]
+fn every_vote_path_completes_an_expired_proposal() {
+	let vote_late = |vote: fn(AccountIdOf<Test>) -> Result<(), sp_runtime::DispatchError>| {
+		new_test_ext().execute_with(|| {
+			let voter = 1;
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+			assert_ok!(start_proposal_helper(voter, 1, 10));
+			assert_ok!(reserve_tokens_helper(voter, 49));
+			assert_ok!(vote_proposal_with_tokens(voter, 1, 16, Vote::Aye));
+
+			System::set_block_number(101);
+			assert_ok!(vote(voter));
+			assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+			System::assert_has_event(
+				Event::LateVoteIgnored { proposal_index: 1, who: voter }.into(),
+			);
+			assert_eq!(AyeVotes::<Test>::get(1, voter), 4);
+		})
+	};
+	vote_late(|voter| {
+		Voting::change_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 9)
+			.map(|_| ())
+			.map_err(|e| e.error)
+	});
+	vote_late(|voter| {
+		Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Aye, 9)
+			.map(|_| ())
+			.map_err(|e| e.error)
+	});
+	vote_late(|voter| {
+		Voting::vote_with_conviction(RuntimeOrigin::signed(voter), 1, 9, 1, Vote::Aye)
+			.map(|_| ())
+			.map_err(|e| e.error)
+	});
+	vote_late(|voter| Voting::commit_vote(RuntimeOrigin::signed(voter), 1, H256::zero()));
+}
+
+#[test]
+fn option_votes_go_through_the_shared_vote_checks() {
+	new_test_ext().execute_with(|| {
+		MaxVotersPerProposal::set(&1);
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Which funding level should we pick?"));
+		let options: Vec<H256> = (1..=2).map(H256::repeat_byte).collect();
+		assert_ok!(Voting::set_proposal_options(RuntimeOrigin::signed(proposer), 1, options));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		for voter in 2..=3 {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 50));
+		}
+
+		assert_ok!(Voting::pause(RawOrigin::Root.into()));
+		assert_noop!(
+			Voting::vote_option(RuntimeOrigin::signed(2), 1, 0, 25),
+			Error::<Test>::PalletPaused
+		);
+		assert_ok!(Voting::resume(RawOrigin::Root.into()));
+
+		// Spreading the tokens across options still counts a single voter
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(2), 1, 0, 25));
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(2), 1, 1, 16));
+		assert_eq!(ProposalVoterCount::<Test>::get(1), 1);
+		assert_eq!(VoteCounts::<Test>::get(1, 2), 2);
+		assert_noop!(
+			Voting::vote_option(RuntimeOrigin::signed(3), 1, 0, 25),
+			Error::<Test>::ProposalVoterCapReached
+		);
+	})
+}
+
+#[test]
+fn unregistering_a_voter_removes_its_option_votes() {
+	new_test_ext().execute_with(|| {
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Which funding level should we pick?"));
+		let options: Vec<H256> = (1..=2).map(H256::repeat_byte).collect();
+		assert_ok!(Voting::set_proposal_options(RuntimeOrigin::signed(proposer), 1, options));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		for voter in 2..=3 {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 49));
+		}
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(2), 1, 0, 49));
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(3), 1, 1, 16));
+		assert_eq!(Voting::get_winning_option(1), Some(0));
+
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), 2));
+
+		assert!(!OptionVotes::<Test>::contains_key((1, 0), 2));
+		assert_eq!(Voting::get_option_votes(1, 0), 0);
+		assert_eq!(Voting::get_option_votes(1, 1), 4);
+		assert_eq!(Voting::get_winning_option(1), Some(1));
+		assert_eq!(ProposalVoterCount::<Test>::get(1), 1);
+	})
+}
+
+#[test]
+fn migrate_option_tallies_to_v6() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(5).put::<Voting>();
+		OptionVotes::<Test>::insert((1, 0), 1, 7);
+		OptionVotes::<Test>::insert((1, 0), 2, 5);
+		OptionVotes::<Test>::insert((1, 1), 3, 4);
+
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(6));
+		assert_eq!(Voting::get_option_votes(1, 0), 12);
+		assert_eq!(Voting::get_option_votes(1, 1), 4);
+	})
+}
+
+#[test]
+fn try_to_delegate_beyond_max_delegators() {
+	new_test_ext().execute_with(|| {
+		MaxDelegators::set(&2);
+		for voter in 1..=4 {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(3), 2));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+		assert_eq!(Voting::delegators_of(&1), vec![2, 3]);
+		// 4 would be the third voter behind 1, through 2
+		assert_noop!(
+			Voting::delegate(RuntimeOrigin::signed(4), 2),
+			Error::<Test>::TooManyDelegators
+		);
+		// Moving 3 within the tree keeps it at two voters
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(3), 1));
+		assert_eq!(Delegators::<Test>::get(1).into_inner(), vec![2, 3]);
+		assert!(Delegators::<Test>::get(2).is_empty());
+	})
+}
+
+#[test]
+fn a_delegator_can_neither_change_nor_reveal_a_vote() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let delegator = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(delegator, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(delegator, 49));
+		let salt = H256::repeat_byte(7);
+		let commitment: H256 = (Vote::Aye, 49u128, salt).using_encoded(blake2_256).into();
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(delegator), 1, commitment));
+		// A hidden vote commits no tokens yet, so the delegation goes through
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(delegator), voter));
+
+		assert_noop!(
+			Voting::change_vote(RuntimeOrigin::signed(delegator), 1, Vote::Nay, 16),
+			Error::<Test>::VotingPowerDelegated
+		);
+		System::set_block_number(101);
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(delegator), 1, Vote::Aye, 49, salt),
+			Error::<Test>::VotingPowerDelegated
+		);
+	})
+}
+
+#[test]
+fn unregistering_a_delegate_gives_the_voting_power_back() {
+	new_test_ext().execute_with(|| {
+		for voter in 1..=3 {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_ok!(reserve_tokens_helper(3, 20));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(3), 2));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 1));
+
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), 2));
+		System::assert_has_event(Event::Undelegated { who: 3 }.into());
+		assert!(Delegations::<Test>::get(3).is_none());
+		assert!(Delegators::<Test>::get(1).is_empty());
+		assert_eq!(Voting::get_available_reserve(&1), 0);
+		assert_eq!(Voting::get_available_reserve(&3), 20);
+	})
+}
+
+#[test]
+fn migrate_the_delegators_index_to_v7() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(6).put::<Voting>();
+		Delegations::<Test>::insert(2, 1);
+		Delegations::<Test>::insert(3, 1);
+		Delegations::<Test>::insert(4, 3);
+
+		migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(7));
+		let mut delegators = Voting::delegators_of(&1);
+		delegators.sort();
+		assert_eq!(delegators, vec![2, 3, 4]);
+		assert_eq!(Delegators::<Test>::get(3).into_inner(), vec![4]);
+	})
+}
+
+#[test]
+fn hidden_votes_are_revealed_before_the_proposal_completes() {
+	new_test_ext().execute_with(|| {
+		RevealPeriod::set(&10);
+		let proposer = 1;
+		let voter = 2;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		let salt = H256::repeat_byte(7);
+		let commitment: H256 = (Vote::Aye, 49u128, salt).using_encoded(blake2_256).into();
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(voter), 1, commitment));
+
+		// The proposal ends at 101 but only completes at 111
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::InProgress));
+		assert_noop!(
+			vote_proposal_with_tokens(proposer, 1, 4, Vote::Nay),
+			Error::<Test>::VotingPeriodEnded
+		);
+		assert_noop!(
+			Voting::close_proposal(RuntimeOrigin::signed(proposer), 1),
+			Error::<Test>::VotingStillActive
+		);
+		System::set_block_number(105);
+		assert_ok!(Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49, salt));
+
+		System::set_block_number(111);
+		Voting::on_finalize(111);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(Voting::get_winner(), Some(1));
+		assert_eq!(ProposalTally::<Test>::get(1), (7, 0));
+	})
+}
+
+#[test]
+fn try_to_reveal_a_vote_after_the_reveal_period() {
+	new_test_ext().execute_with(|| {
+		RevealPeriod::set(&10);
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		let salt = H256::repeat_byte(7);
+		for voter in 2..=3 {
+			assert_ok!(setup_new_voter(voter, 5));
+			assert_ok!(reserve_tokens_helper(voter, 49));
+			let commitment: H256 = (Vote::Aye, 49u128, salt).using_encoded(blake2_256).into();
+			assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(voter), 1, commitment));
+		}
+
+		// Past the reveal period, even before anything completed the proposal
+		System::set_block_number(111);
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(2), 1, Vote::Aye, 49, salt),
+			Error::<Test>::RevealPeriodEnded
+		);
+		assert_ok!(Voting::close_proposal(RuntimeOrigin::signed(proposer), 1));
+		assert_noop!(
+			Voting::reveal_vote(RuntimeOrigin::signed(3), 1, Vote::Aye, 49, salt),
+			Error::<Test>::ProposalNotActive
+		);
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 0));
+	})
+}
+
+#[test]
+fn completions_beyond_the_per_block_cap_move_to_the_next_block() {
+	new_test_ext().execute_with(|| {
+		MaxCompletionsPerBlock::set(&1);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert!(ExpiringProposals::<Test>::contains_key(101, 1));
+		assert!(ExpiringProposals::<Test>::contains_key(102, 2));
+		assert_eq!(ExpiringCount::<Test>::get(101), 1);
+
+		// The completion is paid for upfront
+		assert!(Voting::on_initialize(101).any_gt(Voting::on_initialize(103)));
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(Voting::get_proposal_status(2), Ok(ProposalStatus::InProgress));
+		assert_eq!(ExpiringCount::<Test>::get(101), 0);
+		System::set_block_number(102);
+		Voting::on_finalize(102);
+		assert_eq!(Voting::get_proposal_status(2), Ok(ProposalStatus::Completed));
+	})
+}
+
+#[test]
+fn migrate_expiring_counts_to_v8() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(7).put::<Voting>();
+		ExpiringProposals::<Test>::insert(101, 1, ());
+		ExpiringProposals::<Test>::insert(101, 2, ());
+		ExpiringProposals::<Test>::insert(150, 3, ());
+
+		migrations::v8::MigrateToV8::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(8));
+		assert_eq!(ExpiringCount::<Test>::get(101), 2);
+		assert_eq!(ExpiringCount::<Test>::get(150), 1);
+	})
+}
+
+#[test]
+fn try_to_vote_directly_with_a_pending_commitment() {
+	new_test_ext().execute_with(|| {
+		RevealPeriod::set(&10);
+		let proposer = 1;
+		let voter = 2;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+		let salt = H256::repeat_byte(7);
+		let commitment: H256 = (Vote::Aye, 49u128, salt).using_encoded(blake2_256).into();
+		assert_ok!(Voting::commit_vote(RuntimeOrigin::signed(voter), 1, commitment));
+
+		assert_noop!(
+			vote_proposal_with_tokens(voter, 1, 49, Vote::Nay),
+			Error::<Test>::CommitmentPending
+		);
+		assert_noop!(
+			vote_multiple_proposals_helper(voter, vec![(1, 49, Vote::Aye)]),
+			Error::<Test>::CommitmentPending
+		);
+
+		// Only the revealed vote is counted, with the tokens it committed
+		System::set_block_number(101);
+		assert_ok!(Voting::reveal_vote(RuntimeOrigin::signed(voter), 1, Vote::Aye, 49, salt));
+		assert_eq!(ProposalTally::<Test>::get(1), (7, 0));
+		assert_eq!(CommittedTokens::<Test>::get(voter, 1), 49);
+		assert_eq!(Voting::get_available_reserve(&voter), 0);
+		assert!(!NayVotes::<Test>::contains_key(1, voter));
+	})
+}
+
+#[test]
+fn migrate_the_next_proposal_index_to_v9() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		for text in ["First", "Second", "Third"] {
+			assert_ok!(create_proposal(voter, text));
+		}
+		assert_ok!(Voting::archive_draft(RuntimeOrigin::signed(voter), 3));
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(voter), 1));
+		// Before v9 the index was only stored by the first creation after the upgrade
+		NextProposalIndex::<Test>::kill();
+		StorageVersion::new(8).put::<Voting>();
+
+		migrations::v9::MigrateToV9::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Voting::on_chain_storage_version(), StorageVersion::new(9));
+		assert_eq!(NextProposalIndex::<Test>::get(), 4);
+		assert_ok!(create_proposal(voter, "Fourth"));
+		assert!(Voting::is_proposal_registered(4));
+		assert!(Drafts::<Test>::contains_key(3));
+	})
+}
+
+#[test]
+fn migrating_to_v8_schedules_the_proposals_in_progress() {
+	new_test_ext().execute_with(|| {
+		MaxCompletionsPerBlock::set(&1);
+		let proposer = 1;
+		assert_ok!(setup_new_voter(proposer, 5));
+		for text in ["First", "Second", "Third"] {
+			assert_ok!(create_proposal(proposer, text));
+		}
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		assert_ok!(start_proposal_helper(proposer, 2, 10));
+		// Proposals started before v8 were never scheduled
+		ExpiringProposals::<Test>::drain().for_each(drop);
+		ExpiringCount::<Test>::drain().for_each(drop);
+		StorageVersion::new(7).put::<Voting>();
+
+		migrations::v8::MigrateToV8::<Test>::on_runtime_upgrade();
+
+		assert_eq!(ExpiringProposals::<Test>::iter().count(), 2);
+		assert_eq!(ExpiringCount::<Test>::get(101), 1);
+		assert_eq!(ExpiringCount::<Test>::get(102), 1);
+		for block in 101..=102 {
+			System::set_block_number(block);
+			Voting::on_finalize(block);
+		}
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(Voting::get_proposal_status(2), Ok(ProposalStatus::Completed));
+		assert_eq!(Voting::get_proposal_status(3), Ok(ProposalStatus::NotStarted));
+	})
+}
+
+#[test]
+fn conviction_votes_go_through_the_shared_vote_checks() {
+	new_test_ext().execute_with(|| {
+		MaxProposalReserve::set(&60);
+		let proposer = 1;
+		let voter = 2;
+		let delegator = 3;
+		for account in [proposer, voter, delegator] {
+			assert_ok!(setup_new_voter(account, 5));
+		}
+		assert_ok!(create_proposal(proposer, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+
+		assert_noop!(
+			Voting::vote_with_conviction(RuntimeOrigin::signed(voter), 1, 49, 1, Vote::Abstain),
+			Error::<Test>::ConvictionAbstain
+		);
+		assert_ok!(Voting::pause(RawOrigin::Root.into()));
+		assert_noop!(
+			Voting::vote_with_conviction(RuntimeOrigin::signed(voter), 1, 49, 1, Vote::Nay),
+			Error::<Test>::PalletPaused
+		);
+		assert_ok!(Voting::resume(RawOrigin::Root.into()));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(delegator), voter));
+		assert_noop!(
+			Voting::vote_with_conviction(RuntimeOrigin::signed(delegator), 1, 49, 1, Vote::Nay),
+			Error::<Test>::VotingPowerDelegated
+		);
+
+		// Nays count like ayes, sqrt(49) = 7 doubled by the conviction
+		assert_ok!(Voting::vote_with_conviction(RuntimeOrigin::signed(voter), 1, 49, 1, Vote::Nay));
+		System::assert_last_event(
+			Event::ProposalVoted { proposal_index: 1, vote: Vote::Nay }.into(),
+		);
+		assert_eq!(NayVotes::<Test>::get(1, voter), 14);
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 14));
+		assert_eq!(ProposalCommittedReserve::<Test>::get(1), 49);
+		// The locked tokens count toward MaxProposalReserve
+		assert_noop!(
+			Voting::vote_with_conviction(RuntimeOrigin::signed(proposer), 1, 16, 0, Vote::Aye),
+			Error::<Test>::ProposalReserveCapReached
+		);
+	})
+}
+
+#[test]
+fn change_vote_goes_through_the_shared_vote_checks() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 1, Vote::Aye));
+
+		assert_ok!(Voting::pause(RawOrigin::Root.into()));
+		assert_noop!(
+			Voting::change_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 49),
+			Error::<Test>::PalletPaused
+		);
+		assert_ok!(Voting::resume(RawOrigin::Root.into()));
+		// Rules tightened after the cheap vote still apply to the change
+		AllowSelfVoting::set(&false);
+		assert_noop!(
+			Voting::change_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 49),
+			Error::<Test>::CannotVoteOwnProposal
+		);
+		AllowSelfVoting::set(&true);
+		MinProposalsToVote::set(&2);
+		assert_noop!(
+			Voting::change_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 49),
+			Error::<Test>::MustProposeFirst
+		);
+		MinProposalsToVote::set(&0);
+		assert_ok!(Voting::change_vote(RuntimeOrigin::signed(voter), 1, Vote::Nay, 49));
+		assert_eq!(ProposalTally::<Test>::get(1), (0, 7));
+	})
+}
+
+#[test]
+fn a_refunded_start_fee_is_held_instead_of_minted() {
+	new_test_ext().execute_with(|| {
+		RefundStartFee::set(&true);
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		let issuance = Balances::total_issuance();
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		// The fees stay on the proposer, out of its voting reserve
+		assert_eq!(RoutedFees::get(), 0);
+		assert_eq!(Voting::get_reserved_tokens(&voter), 20);
+		assert_eq!(Voting::get_votable_reserve(&voter), 0);
+
+		// A rejected proposal loses its fee
+		assert_ok!(Voting::reject_proposal(RawOrigin::Root.into(), 2));
+		assert_eq!(RoutedFees::get(), 10);
+		System::set_block_number(101);
+		Voting::on_finalize(101);
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		System::assert_has_event(
+			(Event::StartFeeRefunded { proposal_index: 1, amount: 10 }).into(),
+		);
+		assert_eq!(Voting::get_reserved_tokens(&voter), 0);
+		assert_eq!(Balances::total_balance(&voter), 95 - 10);
+		// Nothing was minted, the mock handler drops the rejected fee
+		assert_eq!(Balances::total_issuance(), issuance - 10);
+	})
+}
+
+#[test]
+fn recompute_pending_end_blocks_keeps_the_custom_durations() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(Voting::create_proposal(
+			RuntimeOrigin::signed(voter),
+			"Blockchain is the future!".as_bytes().to_vec(),
+			Some(200),
+			None
+		));
+		assert_eq!(CustomVotingPeriods::<Test>::get(1), None);
+		assert_eq!(CustomVotingPeriods::<Test>::get(2), Some(200));
+		VotingPeriod::set(&50);
+		assert_ok!(Voting::recompute_pending_end_blocks(RawOrigin::Root.into()));
+		System::assert_last_event((Event::EndBlocksRecomputed { count: 1 }).into());
+		// Only the proposal following VotingPeriod is updated
+		assert_eq!(Voting::get_proposal_end_block(1), Ok(51));
+		assert_eq!(Voting::get_proposal_end_block(2), Ok(201));
+		// The custom duration goes away with the proposal
+		assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(voter), 2));
+		assert_eq!(CustomVotingPeriods::<Test>::get(2), None);
+	})
+}
+
+#[test]
+fn try_to_unreserve_tokens_committed_to_a_vote() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 50));
+		assert_ok!(vote_proposal_with_tokens(voter, 1, 16, Vote::Aye));
+		assert_eq!(Voting::get_committed_tokens(&voter), 16);
+		assert_noop!(unreserve_tokens_helper(voter, 35), Error::<Test>::TokensCommitted);
+		// The tokens that aren't backing the vote can still be unreserved
+		assert_ok!(unreserve_tokens_helper(voter, 34));
+		assert_eq!(Voting::get_reserved_tokens(&voter), 16);
+		assert_eq!(Voting::get_committed_tokens(&voter), 16);
+	})
+}
+
+#[test]
+fn committed_tokens_of_the_option_voters_are_unreserved_on_completion() {
+	new_test_ext().execute_with(|| {
+		AutoUnreserveOnCompletion::set(&true);
+		let proposer = 1;
+		let voter = 2;
+		let late_voter = 3;
+		assert_ok!(setup_new_voter(proposer, 5));
+		assert_ok!(create_proposal(proposer, "Which funding level should we pick?"));
+		let options: Vec<H256> = (1..=3).map(H256::repeat_byte).collect();
+		assert_ok!(Voting::set_proposal_options(RuntimeOrigin::signed(proposer), 1, options));
+		assert_ok!(start_proposal_helper(proposer, 1, 10));
+		for who in [voter, late_voter] {
+			assert_ok!(setup_new_voter(who, 5));
+			assert_ok!(reserve_tokens_helper(who, 40));
+		}
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(voter), 1, 1, 36));
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(voter), 1, 0, 4));
+		// Voting after the end block completes the proposal
+		System::set_block_number(102);
+		assert_ok!(Voting::vote_option(RuntimeOrigin::signed(late_voter), 1, 0, 40));
+		assert_eq!(Voting::get_proposal(1).unwrap().status, ProposalStatus::Completed);
+		// The voter is refunded once, even if it voted several options
+		let refunds = System::events()
+			.into_iter()
+			.filter(|record| {
+				record.event ==
+					RuntimeEvent::Voting(Event::VotesRefunded {
+						proposal_index: 1,
+						who: voter,
+						amount: 40,
+					})
+			})
+			.count();
+		assert_eq!(refunds, 1);
+		assert_eq!(Balances::reserved_balance(&voter), 0);
+		assert_eq!(Voting::get_committed_tokens(&voter), 0);
+		assert_eq!(Balances::reserved_balance(&late_voter), 40);
+	})
+}
+
+#[test]
+fn vote_multiple_proposals_skips_the_expired_entries() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		System::set_block_number(50);
+		assert_ok!(create_proposal(voter, "Blockchain is the future!"));
+		assert_ok!(start_proposal_helper(voter, 2, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+
+		// Only the first proposal is over
+		System::set_block_number(120);
+		assert_ok!(vote_multiple_proposals_helper(
+			voter,
+			vec![(1, 9, Vote::Aye), (2, 16, Vote::Aye)]
+		));
+		System::assert_has_event((Event::LateVoteIgnored { proposal_index: 1, who: voter }).into());
+		System::assert_last_event((Event::BatchVoteOutcome { succeeded: vec![2] }).into());
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(Voting::get_aye_votes_balance(1, &voter), 0);
+		assert_eq!(Voting::get_aye_votes_balance(2, &voter), 4);
+		assert_eq!(Voting::get_committed_tokens(&voter), 16);
+	})
+}
+
+#[test]
+fn winner_outcome_and_election_result_apply_the_quorum() {
+	new_test_ext().execute_with(|| {
+		MinQuorum::set(&2);
+		let voter_1 = 1;
+		let voter_2 = 2;
+		assert_ok!(setup_new_voter(voter_1, 5));
+		assert_ok!(setup_new_voter(voter_2, 5));
+		assert_ok!(create_proposal(voter_1, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter_1, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter_1, 49));
+		assert_ok!(vote_proposal(voter_1, 1, Vote::Aye));
+		// A single voter leads without reaching the quorum
+		assert_eq!(Voting::get_winner_outcome(), WinnerOutcome::NoQuorum(1, 7));
+		assert_eq!(Voting::election_result().winner, None);
+		assert_eq!(Voting::get_winner(), None);
+
+		assert_ok!(reserve_tokens_helper(voter_2, 36));
+		assert_ok!(vote_proposal(voter_2, 1, Vote::Aye));
+		assert_eq!(Voting::get_winner_outcome(), WinnerOutcome::Winner(1, 13));
+		assert_eq!(Voting::election_result().winner, Some((1, 13)));
+	})
+}
+
+#[test]
+fn unregistering_a_sponsor_removes_its_sponsorships() {
+	new_test_ext().execute_with(|| {
+		let sponsor = 1;
+		let beneficiary = 2;
+		let other_sponsor = 3;
+		for voter in [sponsor, beneficiary, other_sponsor] {
+			assert_ok!(setup_new_voter(voter, 5));
+		}
+		assert_ok!(Voting::reserve_for(RuntimeOrigin::signed(sponsor), beneficiary, 30));
+		assert_ok!(Voting::reserve_for(RuntimeOrigin::signed(other_sponsor), beneficiary, 20));
+		assert_eq!(Voting::get_votable_reserve(&beneficiary), 50);
+
+		assert_ok!(Voting::unregister_voter(RawOrigin::Root.into(), sponsor));
+		System::assert_has_event(Event::TokensRefunded { who: sponsor, amount: 30 }.into());
+		assert_eq!(Sponsorships::<Test>::get(sponsor, beneficiary), 0);
+		assert_eq!(SponsoredOut::<Test>::get(sponsor), 0);
+		assert_eq!(Balances::reserved_balance(&sponsor), 0);
+		// Only the tokens of the other sponsor are left to the beneficiary
+		assert_eq!(SponsoredIn::<Test>::get(beneficiary), 20);
+		assert_eq!(Sponsorships::<Test>::get(other_sponsor, beneficiary), 20);
+		assert_eq!(Voting::get_votable_reserve(&beneficiary), 20);
+	})
+}
+
+#[test]
+fn vote_calls_refund_the_weight_of_the_paths_not_taken() {
+	new_test_ext().execute_with(|| {
+		let voter = 1;
+		let delegator = 2;
+		assert_ok!(setup_new_voter(voter, 5));
+		assert_ok!(setup_new_voter(delegator, 5));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(delegator), voter));
+		assert_ok!(create_proposal(voter, "Let's use blockchain to create a better world!"));
+		assert_ok!(start_proposal_helper(voter, 1, 10));
+		assert_ok!(reserve_tokens_helper(voter, 49));
+
+		// The full delegation tree and the late completion are charged up front
+		let call = crate::Call::<Test>::vote_proposal {
+			proposal_index: 1,
+			tokens_to_use: 16,
+			vote: Vote::Aye,
+			conviction: 0,
+		};
+		assert_eq!(
+			call.get_dispatch_info().weight,
+			<() as WeightInfo>::vote_proposal(MaxDelegators::get()).max(Voting::late_vote_weight())
+		);
+		// Only the delegator actually behind the voter is paid for
+		let post_info =
+			Voting::vote_proposal(RuntimeOrigin::signed(voter), 1, 16, Vote::Aye, 0).unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::vote_proposal(1)));
+
+		// A late vote pays for the completion instead
+		System::set_block_number(101);
+		let post_info = Voting::add_votes(RuntimeOrigin::signed(voter), 1, Vote::Aye, 9).unwrap();
+		assert_eq!(Voting::get_proposal_status(1), Ok(ProposalStatus::Completed));
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::late_vote(1, 0)));
+	})
+}
+
+// Helper Functions
+fn setup_new_voter(
+	voter: AccountIdOf<Test>,
+	fee: BalanceOf<Test>,
+) -> Result<(), sp_runtime::DispatchError> {
+	System::set_block_number(1);
+	Voting::register_voter(RawOrigin::Root.into(), voter, fee, false)
+}
+fn reserve_tokens_helper(
+	voter: AccountIdOf<Test>,
+	amount: BalanceOf<Test>,
+) -> Result<(), sp_runtime::DispatchError> {
+	Voting::reserve_tokens(RuntimeOrigin::signed(voter), amount)
+}
+fn unreserve_tokens_helper(
+	voter: AccountIdOf<Test>,
+	amount: BalanceOf<Test>,
+) -> Result<(), sp_runtime::DispatchError> {
+	Voting::unreserve_tokens(RuntimeOrigin::signed(voter), amount)
+}
+fn create_proposal(voter: AccountIdOf<Test>, text: &str) -> Result<(), sp_runtime::DispatchError> {
+	Voting::create_proposal(RuntimeOrigin::signed(voter), text.as_bytes().to_vec(), None, None)
+}
+fn start_proposal_helper(
+	voter: AccountIdOf<Test>,
+	proposal_index: ProposalIndex,
+	fee: BalanceOf<Test>,
+) -> Result<(), sp_runtime::DispatchError> {
+	Voting::start_proposal(RuntimeOrigin::signed(voter), proposal_index, fee)
+}
+// Spends every reserved token still available
+fn vote_proposal(
+	voter: AccountIdOf<Test>,
+	proposal_index: ProposalIndex,
+	vote: Vote,
+) -> Result<(), sp_runtime::DispatchError> {
+	let tokens_to_use = Voting::get_available_reserve(&voter);
+	vote_proposal_with_tokens(voter, proposal_index, tokens_to_use, vote)
+}
+fn vote_proposal_with_tokens(
 	voter: AccountIdOf<Test>,
 	proposal_index: ProposalIndex,
+	tokens_to_use: BalanceOf<Test>,
 	vote: Vote,
 ) -> Result<(), sp_runtime::DispatchError> {
-	Voting::vote_proposal(RuntimeOrigin::signed(voter), proposal_index, vote)
+	Voting::vote_proposal(RuntimeOrigin::signed(voter), proposal_index, tokens_to_use, vote, 0)
+		.map(|_| ())
+		.map_err(|e| e.error)
 }
 fn vote_multiple_proposals_helper(
 	voter: AccountIdOf<Test>,
-	proposals: Vec<(ProposalIndex, BalanceOf<Test>,Vote)>,
+	proposals: Vec<(ProposalIndex, BalanceOf<Test>, Vote)>,
 ) -> Result<(), sp_runtime::DispatchError> {
 	Voting::vote_multiple_proposals(RuntimeOrigin::signed(voter), proposals)
 }
@@ -0,0 +1,792 @@
+//! Weights for pallet_voting
+//!
+//! Estimated from the storage accesses of each call; regenerate them on reference hardware with:
+//! ./target/release/node-template benchmark pallet --chain dev --pallet pallet_voting
+//! --extrinsic '*' --steps 50 --repeat 20 --output pallets/voting/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use core::marker::PhantomData;
+use frame_support::{traits::Get, weights::{constants::RocksDbWeight, Weight}};
+
+/// Weight functions needed for pallet_voting.
+pub trait WeightInfo {
+	fn register_voter() -> Weight;
+	fn create_proposal() -> Weight;
+	fn start_proposal() -> Weight;
+	fn reserve_tokens() -> Weight;
+	fn vote_proposal(d: u32, ) -> Weight;
+	fn unreserve_tokens() -> Weight;
+	fn vote_multiple_proposals(v: u32, ) -> Weight;
+	fn change_vote(d: u32, ) -> Weight;
+	fn complete_proposal(p: u32, ) -> Weight;
+	fn invalidate_result() -> Weight;
+	fn reserve_for() -> Weight;
+	fn recompute_pending_end_blocks(p: u32, ) -> Weight;
+	fn vote_with_conviction() -> Weight;
+	fn unlock_tokens() -> Weight;
+	fn emit_winner(p: u32, ) -> Weight;
+	fn archive_draft() -> Weight;
+	fn restore_draft() -> Weight;
+	fn fund_reward_pool() -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn unregister_voter(p: u32, ) -> Weight;
+	fn delegate(d: u32, ) -> Weight;
+	fn undelegate(d: u32, ) -> Weight;
+	fn commit_vote() -> Weight;
+	fn reveal_vote() -> Weight;
+	fn claim_refund() -> Weight;
+	fn reject_proposal() -> Weight;
+	fn vote_with_credits() -> Weight;
+	fn register_voters(v: u32, ) -> Weight;
+	fn close_proposal(p: u32, ) -> Weight;
+	fn add_votes(d: u32, ) -> Weight;
+	fn set_proposal_options(o: u32, ) -> Weight;
+	fn vote_option(d: u32, ) -> Weight;
+	fn force_start_proposal() -> Weight;
+	fn pause() -> Weight;
+	fn resume() -> Weight;
+	fn extend_proposal() -> Weight;
+	fn late_vote(p: u32, v: u32, ) -> Weight;
+}
+
+/// Weights for pallet_voting using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting RegisteredAt (r:0 w:1)
+	fn register_voter() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting NextProposalIndex (r:1 w:1)
+	// Storage: Voting CounterForProposals (r:1 w:1)
+	// Storage: Voting CounterForDrafts (r:1 w:0)
+	// Storage: Voting ProposalsByProposer (r:1 w:1)
+	// Storage: Voting Proposals (r:0 w:1)
+	// Storage: Voting ProposalTexts (r:0 w:1)
+	fn create_proposal() -> Weight {
+		Weight::from_ref_time(32_000_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting ExpiringProposals (r:0 w:1)
+	fn start_proposal() -> Weight {
+		Weight::from_ref_time(28_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting ReservedAt (r:1 w:1)
+	fn reserve_tokens() -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting ProposalsByProposer (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: Voting SponsoredIn (r:1 w:0)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting AyeVotes (r:1 w:1)
+	// Storage: Voting NayVotes (r:1 w:0)
+	// Storage: Voting AbstainVotes (r:1 w:0)
+	// Storage: Voting ProposalCommittedReserve (r:1 w:1)
+	// Storage: Voting VoteCounts (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting ProposalTally (r:1 w:1)
+	// Storage: Voting VoterBallots (r:1 w:1)
+	// Storage: Voting Delegators (r:d w:0)
+	// Storage: System Account (r:d w:0)
+	// Storage: Voting SponsoredOut (r:d w:0)
+	// Storage: Voting SponsoredIn (r:d w:0)
+	// Storage: Voting HeldDeposits (r:d w:0)
+	// Storage: Voting CommittedTokens (r:d w:0)
+	/// The range of component `d` is `[0, 100]`.
+	fn vote_proposal(d: u32, ) -> Weight {
+		Weight::from_ref_time(64_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(16))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting ReservedAt (r:0 w:1)
+	fn unreserve_tokens() -> Weight {
+		Weight::from_ref_time(32_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting ProposalsByProposer (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting AyeVotes (r:1 w:1)
+	// Storage: Voting NayVotes (r:1 w:0)
+	// Storage: Voting ProposalCommittedReserve (r:1 w:1)
+	// Storage: Voting VoteCounts (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting ProposalTally (r:1 w:1)
+	// Storage: Voting VoterBallots (r:1 w:1)
+	/// The range of component `v` is `[1, 100]`.
+	fn vote_multiple_proposals(v: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(Weight::from_ref_time(36_000_000).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((10_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(v.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting AyeVotes (r:1 w:1)
+	// Storage: Voting NayVotes (r:1 w:1)
+	// Storage: Voting ProposalTally (r:1 w:1)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting ProposalCommittedReserve (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: Voting SponsoredIn (r:1 w:0)
+	// Storage: Voting Delegators (r:d w:0)
+	// Storage: System Account (r:d w:0)
+	// Storage: Voting SponsoredOut (r:d w:0)
+	// Storage: Voting SponsoredIn (r:d w:0)
+	// Storage: Voting HeldDeposits (r:d w:0)
+	// Storage: Voting CommittedTokens (r:d w:0)
+	/// The range of component `d` is `[0, 100]`.
+	fn change_vote(d: u32, ) -> Weight {
+		Weight::from_ref_time(48_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	// Storage: Voting ExpiringCount (r:0 w:1)
+	// Storage: Voting ExpiringProposals (r:1 w:1)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting HeldDeposits (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:0)
+	// Storage: Voting ProposalTally (r:1 w:0)
+	// Storage: Voting RewardedProposals (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Voting Proposals (r:p w:0)
+	/// The range of component `p` is `[1, 1000]`.
+	fn complete_proposal(p: u32, ) -> Weight {
+		Weight::from_ref_time(52_000_000)
+			.saturating_add(Weight::from_ref_time(9_000_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting ExpiringCount (r:1 w:1)
+	// Storage: Voting ExpiringProposals (r:1 w:1)
+	fn invalidate_result() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:2 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Sponsorships (r:1 w:1)
+	// Storage: Voting SponsoredOut (r:1 w:1)
+	// Storage: Voting SponsoredIn (r:1 w:1)
+	// Storage: Voting ReservedAt (r:1 w:1)
+	fn reserve_for() -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: Voting Proposals (r:p w:p)
+	/// The range of component `p` is `[1, 1000]`.
+	fn recompute_pending_end_blocks(p: u32, ) -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(Weight::from_ref_time(6_000_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting ProposalsByProposer (r:1 w:0)
+	// Storage: Voting AyeVotes (r:1 w:1)
+	// Storage: Voting NayVotes (r:1 w:0)
+	// Storage: Voting VoteCounts (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:1)
+	// Storage: Voting VoterBallots (r:1 w:1)
+	// Storage: Voting ProposalTally (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting ConvictionLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn vote_with_conviction() -> Weight {
+		Weight::from_ref_time(70_000_000)
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	// Storage: Voting ConvictionLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock_tokens() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Proposals (r:p w:0)
+	// Storage: Voting ProposalTally (r:p w:0)
+	// Storage: Voting ProposalVoterCount (r:p w:0)
+	/// The range of component `p` is `[1, 1000]`.
+	fn emit_winner(p: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000)
+			.saturating_add(Weight::from_ref_time(8_000_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(p.into())))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting CounterForProposals (r:1 w:1)
+	// Storage: Voting CounterForDrafts (r:1 w:1)
+	// Storage: Voting Drafts (r:0 w:1)
+	fn archive_draft() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Drafts (r:1 w:1)
+	// Storage: Voting CounterForDrafts (r:1 w:1)
+	// Storage: Voting CounterForProposals (r:1 w:1)
+	// Storage: Voting Proposals (r:0 w:1)
+	fn restore_draft() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: System Account (r:2 w:2)
+	fn fund_reward_pool() -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting CounterForProposals (r:1 w:1)
+	// Storage: Voting HeldDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting ProposalsByProposer (r:1 w:1)
+	// Storage: Voting ProposerIndex (r:1 w:1)
+	// Storage: Voting ProposalTexts (r:0 w:1)
+	fn cancel_proposal() -> Weight {
+		Weight::from_ref_time(36_000_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: Voting VoterBallots (r:1 w:1)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: Voting HeldDeposits (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Delegations (r:1 w:1)
+	// Storage: Voting Delegators (r:2 w:2)
+	// Storage: Voting ReservedAt (r:0 w:1)
+	// Storage: Voting ReserveLocks (r:0 w:1)
+	// Storage: Voting RegisteredAt (r:0 w:1)
+	// Storage: Voting VotedProposals (r:0 w:1)
+	// Storage: Voting Proposals (r:p w:0)
+	// Storage: Voting AyeVotes (r:p w:p)
+	// Storage: Voting NayVotes (r:p w:p)
+	// Storage: Voting AbstainVotes (r:p w:p)
+	// Storage: Voting VoteCounts (r:p w:p)
+	// Storage: Voting ProposalTally (r:p w:p)
+	// Storage: Voting ProposalVoterCount (r:p w:p)
+	/// The range of component `p` is `[1, 1000]`.
+	fn unregister_voter(p: u32, ) -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(Weight::from_ref_time(20_000_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(11))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(p.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:2 w:0)
+	// Storage: Voting CommittedTokens (r:1 w:0)
+	// Storage: Voting Delegations (r:d w:1)
+	// Storage: Voting Delegators (r:d w:2)
+	/// The range of component `d` is `[0, 99]`.
+	fn delegate(d: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(Weight::from_ref_time(4_000_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Delegations (r:d w:1)
+	// Storage: Voting CommittedTokens (r:d w:0)
+	// Storage: Voting Delegators (r:1 w:1)
+	/// The range of component `d` is `[1, 100]`.
+	fn undelegate(d: u32, ) -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(Weight::from_ref_time(4_000_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting ProposalsByProposer (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting AyeVotes (r:1 w:0)
+	// Storage: Voting NayVotes (r:1 w:0)
+	// Storage: Voting AbstainVotes (r:1 w:0)
+	// Storage: Voting VoteCommitments (r:1 w:1)
+	fn commit_vote() -> Weight {
+		Weight::from_ref_time(36_000_000)
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting VoteCommitments (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:1)
+	// Storage: Voting VoterBallots (r:1 w:1)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: Voting SponsoredIn (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Voting ProposalCommittedReserve (r:1 w:1)
+	// Storage: Voting ProposalTally (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting AyeVotes (r:0 w:1)
+	// Storage: Voting VoteCounts (r:0 w:1)
+	fn reveal_vote() -> Weight {
+		Weight::from_ref_time(70_000_000)
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting ConvictionLocks (r:1 w:0)
+	// Storage: Voting ReserveLocks (r:1 w:0)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting ReservedAt (r:0 w:1)
+	fn claim_refund() -> Weight {
+		Weight::from_ref_time(36_000_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting HeldDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_proposal() -> Weight {
+		Weight::from_ref_time(32_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting ReservedAt (r:1 w:1)
+	// Storage: Voting ProposalsByProposer (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: Voting SponsoredIn (r:1 w:0)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting AyeVotes (r:1 w:1)
+	// Storage: Voting NayVotes (r:1 w:0)
+	// Storage: Voting AbstainVotes (r:1 w:0)
+	// Storage: Voting ProposalCommittedReserve (r:1 w:1)
+	// Storage: Voting VoteCounts (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting ProposalTally (r:1 w:1)
+	// Storage: Voting VoterBallots (r:1 w:1)
+	// Storage: Voting Paused (r:1 w:0)
+	fn vote_with_credits() -> Weight {
+		Weight::from_ref_time(88_000_000)
+			.saturating_add(T::DbWeight::get().reads(18))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	// Storage: Voting RegisteredVoters (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Voting RegisteredAt (r:0 w:v)
+	/// The range of component `v` is `[1, 100]`.
+	fn register_voters(v: u32, ) -> Weight {
+		Weight::from_ref_time(8_000_000)
+			.saturating_add(Weight::from_ref_time(24_000_000).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(v.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting HeldDeposits (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:0)
+	// Storage: Voting ProposalTally (r:1 w:0)
+	// Storage: Voting RewardedProposals (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Voting CounterForProposals (r:1 w:0)
+	// Storage: Voting Proposals (r:p w:0)
+	// Storage: Voting ProposalTally (r:p w:0)
+	/// The range of component `p` is `[1, 1000]`.
+	fn close_proposal(p: u32, ) -> Weight {
+		Weight::from_ref_time(56_000_000)
+			.saturating_add(Weight::from_ref_time(9_000_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting ProposalsByProposer (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: Voting SponsoredIn (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting AyeVotes (r:1 w:1)
+	// Storage: Voting NayVotes (r:1 w:0)
+	// Storage: Voting ProposalCommittedReserve (r:1 w:1)
+	// Storage: Voting ProposalTally (r:1 w:1)
+	// Storage: Voting VoteCounts (r:1 w:0)
+	// Storage: Voting Delegators (r:d w:0)
+	// Storage: System Account (r:d w:0)
+	// Storage: Voting SponsoredOut (r:d w:0)
+	// Storage: Voting SponsoredIn (r:d w:0)
+	// Storage: Voting HeldDeposits (r:d w:0)
+	// Storage: Voting CommittedTokens (r:d w:0)
+	/// The range of component `d` is `[0, 100]`.
+	fn add_votes(d: u32, ) -> Weight {
+		Weight::from_ref_time(60_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	/// The range of component `o` is `[0, 10]`.
+	fn set_proposal_options(o: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(Weight::from_ref_time(1_000_000).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting Paused (r:1 w:0)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting ProposalsByProposer (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting SponsoredOut (r:1 w:0)
+	// Storage: Voting SponsoredIn (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: Voting CommittedTokens (r:1 w:1)
+	// Storage: Voting ProposalCommittedReserve (r:1 w:1)
+	// Storage: Voting OptionVotes (r:1 w:1)
+	// Storage: Voting OptionTally (r:1 w:1)
+	// Storage: Voting VoteCounts (r:1 w:1)
+	// Storage: Voting ProposalVoterCount (r:1 w:1)
+	// Storage: Voting VoterBallots (r:1 w:1)
+	// Storage: Voting VotedProposals (r:1 w:1)
+	// Storage: Voting Delegators (r:d w:0)
+	// Storage: System Account (r:d w:0)
+	// Storage: Voting SponsoredOut (r:d w:0)
+	// Storage: Voting SponsoredIn (r:d w:0)
+	// Storage: Voting HeldDeposits (r:d w:0)
+	// Storage: Voting CommittedTokens (r:d w:0)
+	/// The range of component `d` is `[0, 100]`.
+	fn vote_option(d: u32, ) -> Weight {
+		Weight::from_ref_time(62_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(17))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting ExpiringCount (r:1 w:1)
+	// Storage: Voting ExpiringProposals (r:1 w:1)
+	fn force_start_proposal() -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Paused (r:0 w:1)
+	fn pause() -> Weight {
+		Weight::from_ref_time(10_000_000)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting Paused (r:0 w:1)
+	fn resume() -> Weight {
+		Weight::from_ref_time(10_000_000)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting ExpiringCount (r:1 w:1)
+	// Storage: Voting ExpiringProposals (r:1 w:1)
+	fn extend_proposal() -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Paused (r:1 w:0)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting ExpiringProposals (r:1 w:1)
+	// Storage: Voting HeldDeposits (r:1 w:1)
+	// Storage: Voting HeldStartFees (r:1 w:1)
+	// Storage: Voting RewardedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting CounterForProposals (r:1 w:0)
+	// Storage: Voting Proposals (r:p w:0)
+	// Storage: Voting ProposalTally (r:p w:0)
+	// Storage: Voting ProposalVoterCount (r:p w:0)
+	// Storage: Voting AyeVotes (r:v w:0)
+	// Storage: Voting ReserveLocks (r:v w:0)
+	// Storage: Voting CommittedTokens (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Voting VotedProposals (r:v w:v)
+	// Storage: Voting ReservedAt (r:0 w:v)
+	/// The range of component `p` is `[1, 1000]`.
+	/// The range of component `v` is `[0, 10000]`.
+	fn late_vote(p: u32, v: u32, ) -> Weight {
+		Weight::from_ref_time(60_000_000)
+			.saturating_add(Weight::from_ref_time(9_000_000).saturating_mul(p.into()))
+			.saturating_add(Weight::from_ref_time(30_000_000).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(v.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn register_voter() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn create_proposal() -> Weight {
+		Weight::from_ref_time(32_000_000)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	fn start_proposal() -> Weight {
+		Weight::from_ref_time(28_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn reserve_tokens() -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn vote_proposal(d: u32, ) -> Weight {
+		Weight::from_ref_time(64_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(16))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(10))
+	}
+	fn unreserve_tokens() -> Weight {
+		Weight::from_ref_time(32_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn vote_multiple_proposals(v: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(Weight::from_ref_time(36_000_000).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().reads((10_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(v.into())))
+	}
+	fn change_vote(d: u32, ) -> Weight {
+		Weight::from_ref_time(48_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(10))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	fn complete_proposal(p: u32, ) -> Weight {
+		Weight::from_ref_time(52_000_000)
+			.saturating_add(Weight::from_ref_time(9_000_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(7))
+	}
+	fn invalidate_result() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn reserve_for() -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	fn recompute_pending_end_blocks(p: u32, ) -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(Weight::from_ref_time(6_000_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	fn vote_with_conviction() -> Weight {
+		Weight::from_ref_time(70_000_000)
+			.saturating_add(RocksDbWeight::get().reads(13))
+			.saturating_add(RocksDbWeight::get().writes(9))
+	}
+	fn unlock_tokens() -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn emit_winner(p: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000)
+			.saturating_add(Weight::from_ref_time(8_000_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(p.into())))
+	}
+	fn archive_draft() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	fn restore_draft() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	fn fund_reward_pool() -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn cancel_proposal() -> Weight {
+		Weight::from_ref_time(36_000_000)
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(7))
+	}
+	fn unregister_voter(p: u32, ) -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(Weight::from_ref_time(20_000_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(11))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(p.into())))
+	}
+	fn delegate(d: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000)
+			.saturating_add(Weight::from_ref_time(4_000_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn undelegate(d: u32, ) -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(Weight::from_ref_time(4_000_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn commit_vote() -> Weight {
+		Weight::from_ref_time(36_000_000)
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn reveal_vote() -> Weight {
+		Weight::from_ref_time(70_000_000)
+			.saturating_add(RocksDbWeight::get().reads(14))
+			.saturating_add(RocksDbWeight::get().writes(9))
+	}
+	fn claim_refund() -> Weight {
+		Weight::from_ref_time(36_000_000)
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	fn reject_proposal() -> Weight {
+		Weight::from_ref_time(32_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn vote_with_credits() -> Weight {
+		Weight::from_ref_time(88_000_000)
+			.saturating_add(RocksDbWeight::get().reads(18))
+			.saturating_add(RocksDbWeight::get().writes(10))
+	}
+	fn register_voters(v: u32, ) -> Weight {
+		Weight::from_ref_time(8_000_000)
+			.saturating_add(Weight::from_ref_time(24_000_000).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(v.into())))
+	}
+	fn close_proposal(p: u32, ) -> Weight {
+		Weight::from_ref_time(56_000_000)
+			.saturating_add(Weight::from_ref_time(9_000_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	fn add_votes(d: u32, ) -> Weight {
+		Weight::from_ref_time(60_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(14))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	fn set_proposal_options(o: u32, ) -> Weight {
+		Weight::from_ref_time(20_000_000)
+			.saturating_add(Weight::from_ref_time(1_000_000).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn vote_option(d: u32, ) -> Weight {
+		Weight::from_ref_time(62_000_000)
+			.saturating_add(Weight::from_ref_time(12_000_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(17))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(8))
+	}
+	fn force_start_proposal() -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn pause() -> Weight {
+		Weight::from_ref_time(10_000_000)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn resume() -> Weight {
+		Weight::from_ref_time(10_000_000)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn extend_proposal() -> Weight {
+		Weight::from_ref_time(26_000_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn late_vote(p: u32, v: u32, ) -> Weight {
+		Weight::from_ref_time(60_000_000)
+			.saturating_add(Weight::from_ref_time(9_000_000).saturating_mul(p.into()))
+			.saturating_add(Weight::from_ref_time(30_000_000).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(6))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(v.into())))
+	}
+}
@@ -42,7 +42,7 @@ pub use frame_support::{
 		},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -50,7 +50,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -71,7 +71,8 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
-
+/// Index of a proposal in pallet-voting.
+pub type ProposalIndex = u32;
 
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
@@ -311,6 +312,51 @@ impl pallet_assets::Config for Runtime {
 
 parameter_types! {
 	pub const VotingPeriod : BlockNumber = 1 * HOURS;
+	pub const RequirePreReserve: bool = false;
+	pub const RegistrationIdempotent: bool = false;
+	pub const MinProposalsToVote: u32 = 0;
+	pub const PassThreshold: u128 = 0;
+	pub const DisputeWindow: BlockNumber = 10 * MINUTES;
+	pub const ReserveYield: Percent = Percent::from_percent(0);
+	pub const YieldPeriod: BlockNumber = 1 * HOURS;
+	pub const MaxYieldPayoutsPerBlock: u32 = 100;
+	pub const AgeWeighting: bool = false;
+	pub const MaxAgeMultiplier: u32 = 4;
+	pub const RefundStartFee: bool = false;
+	pub const WeightDecay: Percent = Percent::from_percent(0);
+	pub const LockPeriodLength: BlockNumber = 1 * HOURS;
+	pub const MaxLockPeriods: u32 = 6;
+	pub const AllowRepeatVotes: bool = false;
+	pub const RepeatVoteDiscount: Percent = Percent::from_percent(50);
+	pub const MaxConcentrationVoters: u32 = 1_000;
+	pub const AutoUnreserveOnCompletion: bool = false;
+	pub const MaxProposalReserve: Balance = Balance::MAX;
+	pub const MaxVoted: u32 = 100;
+	pub const WinMargin: Percent = Percent::from_percent(0);
+	pub const MaxVotersPerProposal: u32 = 10_000;
+	pub const MaxBulkStatusAccounts: u32 = 100;
+	pub const ProposalStartDeadline: BlockNumber = 7 * DAYS;
+	pub const RejectEmptyText: bool = true;
+	pub const VotingPalletId: PalletId = PalletId(*b"py/votng");
+	pub const PoolReward: Balance = 0;
+	pub const MaxProposalTextLen: u32 = 1024;
+	pub const MinQuorum: u32 = 1;
+	pub const WinnerReward: Balance = 0;
+	pub const MaxProposals: u32 = 1_000;
+	pub const MinVotingPeriod: BlockNumber = 10 * MINUTES;
+	pub const MaxVotingPeriod: BlockNumber = 7 * DAYS;
+	pub const AllowSelfVoting: bool = false;
+	pub const MaxBatchSize: u32 = 100;
+	pub const MaxVotesPerVoter: u32 = u32::MAX;
+	pub const MaxOptions: u32 = 10;
+	pub const MinReserveAmount: Balance = 10;
+	pub const MaxMetadataLen: u32 = 256;
+	pub const ProposalCreationFee: Balance = 0;
+	pub const ProposalDeposit: Balance = 0;
+	pub const MinProposerReserve: Balance = 0;
+	pub const MaxDelegators: u32 = 100;
+	pub const RevealPeriod: BlockNumber = 10 * MINUTES;
+	pub const MaxCompletionsPerBlock: u32 = 20;
 }
 
 /// Configure the pallet-voting in pallets/voting.0
@@ -318,6 +364,54 @@ impl pallet_voting::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type VotingPeriod = VotingPeriod;
+	type RequirePreReserve = RequirePreReserve;
+	type RegistrationIdempotent = RegistrationIdempotent;
+	type MinProposalsToVote = MinProposalsToVote;
+	type PassThreshold = PassThreshold;
+	type DisputeWindow = DisputeWindow;
+	type ReserveYield = ReserveYield;
+	type YieldPeriod = YieldPeriod;
+	type MaxYieldPayoutsPerBlock = MaxYieldPayoutsPerBlock;
+	type AgeWeighting = AgeWeighting;
+	type MaxAgeMultiplier = MaxAgeMultiplier;
+	type RefundStartFee = RefundStartFee;
+	type WeightDecay = WeightDecay;
+	type LockPeriodLength = LockPeriodLength;
+	type MaxLockPeriods = MaxLockPeriods;
+	type AllowRepeatVotes = AllowRepeatVotes;
+	type RepeatVoteDiscount = RepeatVoteDiscount;
+	type MaxConcentrationVoters = MaxConcentrationVoters;
+	type AutoUnreserveOnCompletion = AutoUnreserveOnCompletion;
+	type MaxProposalReserve = MaxProposalReserve;
+	type MaxVoted = MaxVoted;
+	type WinMargin = WinMargin;
+	type MaxVotersPerProposal = MaxVotersPerProposal;
+	type MaxBulkStatusAccounts = MaxBulkStatusAccounts;
+	type ProposalStartDeadline = ProposalStartDeadline;
+	type RejectEmptyText = RejectEmptyText;
+	type PalletId = VotingPalletId;
+	type PoolReward = PoolReward;
+	type MaxProposalTextLen = MaxProposalTextLen;
+	type MinQuorum = MinQuorum;
+	type WinnerReward = WinnerReward;
+	type FeeHandler = ();
+	type MaxProposals = MaxProposals;
+	type MinVotingPeriod = MinVotingPeriod;
+	type MaxVotingPeriod = MaxVotingPeriod;
+	type AllowSelfVoting = AllowSelfVoting;
+	type MaxBatchSize = MaxBatchSize;
+	type ProposalIndex = ProposalIndex;
+	type MaxVotesPerVoter = MaxVotesPerVoter;
+	type MaxOptions = MaxOptions;
+	type MinReserveAmount = MinReserveAmount;
+	type MaxMetadataLen = MaxMetadataLen;
+	type ProposalCreationFee = ProposalCreationFee;
+	type ProposalDeposit = ProposalDeposit;
+	type MinProposerReserve = MinProposerReserve;
+	type MaxDelegators = MaxDelegators;
+	type RevealPeriod = RevealPeriod;
+	type MaxCompletionsPerBlock = MaxCompletionsPerBlock;
+	type WeightInfo = pallet_voting::weights::SubstrateWeight<Runtime>;
 }
 
 pub struct AuthorityToAccount;
@@ -391,6 +485,18 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations to run on runtime upgrade.
+pub type Migrations = (
+	pallet_voting::migrations::v1::MigrateToV1<Runtime>,
+	pallet_voting::migrations::v2::MigrateToV2<Runtime>,
+	pallet_voting::migrations::v3::MigrateToV3<Runtime>,
+	pallet_voting::migrations::v4::MigrateToV4<Runtime>,
+	pallet_voting::migrations::v5::MigrateToV5<Runtime>,
+	pallet_voting::migrations::v6::MigrateToV6<Runtime>,
+	pallet_voting::migrations::v7::MigrateToV7<Runtime>,
+	pallet_voting::migrations::v8::MigrateToV8<Runtime>,
+	pallet_voting::migrations::v9::MigrateToV9<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -398,6 +504,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -566,6 +673,50 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl
+		pallet_voting_runtime_api::VotingApi<
+			Block,
+			AccountId,
+			Balance,
+			ProposalIndex,
+			pallet_voting::ElectionResult<Runtime>,
+			pallet_voting::WinnerOutcome<ProposalIndex>,
+		> for Runtime
+	{
+		fn bulk_voting_status(
+			accounts: Vec<AccountId>,
+			proposal_index: ProposalIndex,
+		) -> Vec<(AccountId, bool, bool)> {
+			Voting::bulk_voting_status(accounts, proposal_index)
+		}
+
+		fn election_result() -> pallet_voting::ElectionResult<Runtime> {
+			Voting::election_result()
+		}
+
+		fn current_winner() -> Option<ProposalIndex> {
+			Voting::get_winner()
+		}
+
+		fn get_winner_outcome() -> pallet_voting::WinnerOutcome<ProposalIndex> {
+			Voting::get_winner_outcome()
+		}
+
+		fn proposal_tally(
+			proposal_index: ProposalIndex,
+		) -> Option<(Balance, Balance)> {
+			Voting::proposal_tally(proposal_index)
+		}
+
+		fn active_proposals() -> Vec<ProposalIndex> {
+			Voting::active_proposals()
+		}
+
+		fn is_voting_open(proposal_index: ProposalIndex) -> bool {
+			Voting::is_voting_open(proposal_index)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (